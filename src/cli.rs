@@ -0,0 +1,202 @@
+//! # Command-line interface
+//!
+//! `cli` contains the functions that generate, solve and check bineros without the GUI
+
+use std::{fs, io::{self, Read}};
+use tr::tr;
+use enum_iterator::all;
+use yabinero::engine::{Binero, pack::Pack};
+use yabinero::enums::{Difficulty, Size};
+use crate::translation::Translate;
+
+/// Runs the command given on the command line and returns the exit code of the program
+///
+/// # Arguments
+///
+/// * `args` - the arguments given on the command line, without the name of the program
+pub fn run(args: &[String]) -> i32 {
+    let result = match args.first().map(String::as_str) {
+        Some("generate") => generate(&args[1..]),
+        Some("solve") => solve(&args[1..]),
+        Some("check") => check(&args[1..]),
+        Some("help" | "--help" | "-h") => {
+            println!("{}", usage());
+            Ok(())
+        },
+        Some(command) => Err(Error::Usage(tr!("Unknown command: {}", command))),
+        None => Err(Error::Usage(tr!("Missing command"))),
+    };
+    match result {
+        Ok(()) => 0,
+        Err(Error::Usage(msg)) => {
+            eprintln!("{}\n\n{}", msg, usage());
+            EXIT_USAGE
+        },
+        Err(Error::Puzzle(msg)) => {
+            eprintln!("{}", msg);
+            EXIT_PUZZLE
+        },
+    }
+}
+
+/// The errors that stop a command
+enum Error {
+    Usage(String),
+    Puzzle(String),
+}
+
+/// The formats in which a binero can be printed
+#[derive(Clone, Copy)]
+enum Format {
+    Text,
+    Line,
+    Json,
+    Pack,
+}
+
+impl Format {
+    /// Returns the `Format` corresponding to a given string slice
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - a string slice representing a format
+    fn from_str(format: &str) -> Option<Format> {
+        match format {
+            "text" => Some(Format::Text),
+            "line" => Some(Format::Line),
+            "json" => Some(Format::Json),
+            "pack" => Some(Format::Pack),
+            _ => None,
+        }
+    }
+
+    /// Returns a binero printed in the format, a binero alone being printed in JSON when the
+    /// format is a pack
+    ///
+    /// # Arguments
+    ///
+    /// * `binero` - a binero
+    fn print(&self, binero: &Binero) -> String {
+        match *self {
+            Format::Text => binero.givens().to_text(),
+            Format::Line => format!("{}\n", binero.to_compact_string()),
+            Format::Json | Format::Pack => format!("{}\n", binero.to_json()),
+        }
+    }
+}
+
+/// Prints generated bineros
+///
+/// # Arguments
+///
+/// * `args` - the options of the command
+fn generate(args: &[String]) -> Result<(), Error> {
+    let mut size = Size::square(8).unwrap();
+    let mut difficulty = Difficulty::Medium;
+    let mut count: u32 = 1;
+    let mut seed: Option<u64> = None;
+    let mut format = Format::Text;
+    let mut name = String::from("yabinero");
+    let mut iter = args.iter();
+    while let Some(option) = iter.next() {
+        let value = iter.next().ok_or_else(|| Error::Usage(tr!("Missing value for {}", option)))?;
+        let invalid = || Error::Usage(tr!("Invalid value for {}: {}", option, value));
+        match option.as_str() {
+            "--size" => size = value.parse().map_err(|_| invalid())?,
+            "--difficulty" => difficulty = parse_difficulty(value).ok_or_else(invalid)?,
+            "--count" => count = value.parse().map_err(|_| invalid())?,
+            "--seed" => seed = Some(value.parse().map_err(|_| invalid())?),
+            "--format" => format = Format::from_str(value).ok_or_else(invalid)?,
+            "--name" => name = value.clone(),
+            _ => return Err(Error::Usage(tr!("Unknown option: {}", option))),
+        }
+    }
+    let binero_at = |i: u32| Binero::new(size, difficulty, seed.map(|seed| seed.wrapping_add(i as u64)));
+    match format {
+        Format::Pack => {
+            let bineros: Vec<Binero> = (0..count).map(binero_at).collect();
+            println!("{}", Pack::new(&name, &bineros).to_json());
+        },
+        _ => for i in 0..count {
+            if i > 0 {
+                println!();
+            }
+            print!("{}", format.print(&binero_at(i)));
+        },
+    }
+    Ok(())
+}
+
+/// Prints the solution of a binero read from a file
+///
+/// # Arguments
+///
+/// * `args` - the path of the file, or `-` for the standard input
+fn solve(args: &[String]) -> Result<(), Error> {
+    let binero = read_binero(args)?;
+    print!("{}", binero.solution().to_text());
+    Ok(())
+}
+
+/// Prints whether or not a binero read from a file has exactly one solution, and its rating
+///
+/// # Arguments
+///
+/// * `args` - the path of the file, or `-` for the standard input
+fn check(args: &[String]) -> Result<(), Error> {
+    let binero = read_binero(args)?;
+    let rating = binero.rating();
+    println!("{}", tr!("The grid has exactly one solution."));
+    println!("{}", tr!("Difficulty: {}", binero.difficulty().translate()));
+    if let Some(technique) = rating.hardest() {
+        println!("{}", tr!("Hardest technique: {}", technique.translate()));
+    }
+    println!("{}", tr!("Guesses: {}", rating.guesses()));
+    Ok(())
+}
+
+/// Returns the binero read from the file given on the command line
+///
+/// # Arguments
+///
+/// * `args` - the path of the file, or `-` for the standard input
+fn read_binero(args: &[String]) -> Result<Binero, Error> {
+    let path = match args {
+        [path] => path,
+        _ => return Err(Error::Usage(tr!("Exactly one file is expected"))),
+    };
+    let text = if path == "-" {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text).map(|_| text)
+    } else {
+        fs::read_to_string(path)
+    };
+    let text = text.map_err(|_| Error::Puzzle(tr!("The file cannot be read!")))?;
+    text.parse::<Binero>().map_err(|error| Error::Puzzle(error.translate()))
+}
+
+/// Returns the `Difficulty` corresponding to a given string slice, ignoring the case
+///
+/// # Arguments
+///
+/// * `difficulty` - a string slice representing a difficulty
+fn parse_difficulty(difficulty: &str) -> Option<Difficulty> {
+    all::<Difficulty>().find(|a_difficulty| format!("{:?}", a_difficulty).eq_ignore_ascii_case(difficulty))
+}
+
+/// Returns the help of the command-line interface
+fn usage() -> String {
+    let mut result = tr!("Usage:");
+    result.push_str("
+  yabinero
+  yabinero generate [--size N|WxH] [--difficulty beginner|easy|medium|hard] [--count N] [--seed N] [--format text|line|json|pack] [--name NAME]
+  yabinero solve FILE
+  yabinero check FILE
+
+");
+    result.push_str(&tr!("FILE contains one line per row, with 0, 1 and . for the empty boxes, or a single line as printed with --format line. Use - to read the standard input."));
+    result
+}
+
+const EXIT_PUZZLE: i32 = 1;
+const EXIT_USAGE: i32 = 2;
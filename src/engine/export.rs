@@ -0,0 +1,44 @@
+//! # Export
+//!
+//! `export` contains the functions that turn a binero into a text that can be archived or shared
+
+use serde_json::json;
+use crate::engine::Binero;
+
+impl Binero {
+    /// Returns the binero as a single line, made of its size and its givens
+    pub fn to_compact_string(&self) -> String {
+        format!("{}{}{}", self.size(), Binero::COMPACT_SEPARATOR, self.givens.to_compact_string())
+    }
+
+    /// Returns the binero as text grids, with one line per row: its givens, then its solution and
+    /// the values put by the player, each after a blank line and a title
+    ///
+    /// Reading the text back with `Binero::from_str` only reads the givens.
+    pub fn to_text(&self) -> String {
+        format!("{}\n{}\n{}\n{}\n{}", self.givens.to_text(), Binero::SOLUTION_TITLE, self.solution.to_text(), Binero::GRID_TITLE, self.grid.to_text())
+    }
+
+    /// Returns the binero as a JSON document, with its size, difficulty, seed, givens, solution
+    /// and the values put by the player
+    pub fn to_json(&self) -> String {
+        let document = json!({
+            "size": format!("{}", self.size()),
+            "difficulty": format!("{:?}", self.difficulty),
+            "seed": self.seed,
+            "givens": self.givens.rows(),
+            "solution": self.solution.rows(),
+            "grid": self.grid.rows(),
+        });
+        serde_json::to_string_pretty(&document).unwrap()
+    }
+
+    /// The separator of the size and the givens in the line made by `Binero::to_compact_string`
+    pub const COMPACT_SEPARATOR: char = ':';
+
+    /// The title of the solution in the text made by `Binero::to_text`
+    const SOLUTION_TITLE: &'static str = "# Solution";
+
+    /// The title of the values put by the player in the text made by `Binero::to_text`
+    const GRID_TITLE: &'static str = "# Grid";
+}
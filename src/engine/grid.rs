@@ -5,10 +5,15 @@
 use std::fmt;
 use crate::enums::{Size, Value};
 
-/// A binero grid is represented here
+/// A set of boxes of a row or a column: the nth bit stands for the nth box
+type Mask = u32;
+
+/// A binero grid is represented here, each row and each column being stored as bitmasks
+#[derive(Clone)]
 pub struct Grid {
     size: Size,
-    matrix: Vec<Vec<Option<Value>>>,
+    rows: [LineBits; Grid::MAX_SIDE],
+    columns: [LineBits; Grid::MAX_SIDE],
     empty_values: u16,
 }
 
@@ -19,12 +24,69 @@ impl Grid {
     ///
     /// * `size` - a size
     pub fn new(size: Size) -> Grid {
-        let size_u8 = size.as_u8();
         Grid {
             size,
-            matrix: vec![vec![None; size_u8 as usize]; size_u8 as usize],
-            empty_values: (size_u8 as u16).pow(2),
+            rows: [LineBits::default(); Grid::MAX_SIDE],
+            columns: [LineBits::default(); Grid::MAX_SIDE],
+            empty_values: size.width() as u16 * size.height() as u16,
+        }
+    }
+
+    /// Returns a binero grid with the given size filled from a string of values and `Grid::EMPTY_CHAR`,
+    /// row after row, or `None` if the string does not match the size
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - a size
+    /// * `compact` - a string slice representing the values of the grid
+    pub fn from_compact_str(size: Size, compact: &str) -> Option<Grid> {
+        let chars: Vec<char> = compact.chars().collect();
+        if chars.len() != size.width() as usize * size.height() as usize {
+            return None;
+        }
+        let mut result = Grid::new(size);
+        for i in 0..size.height() {
+            for j in 0..size.width() {
+                let c = chars[i as usize * size.width() as usize + j as usize];
+                if c != Grid::EMPTY_CHAR {
+                    result.put(i, j, Some(Value::from_char(c)?));
+                }
+            }
         }
+        Some(result)
+    }
+
+    /// Returns the values of the grid as a string, row after row, with `Grid::EMPTY_CHAR` for the
+    /// empty boxes
+    pub fn to_compact_string(&self) -> String {
+        let mut result = String::new();
+        for i in 0..self.size.height() {
+            for j in 0..self.size.width() {
+                match self.get(i, j) {
+                    Some(val) => result.push_str(&format!("{}", val)),
+                    None => result.push(Grid::EMPTY_CHAR),
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns the values of the grid as a text, with one line per row and `Grid::EMPTY_CHAR` for
+    /// the empty boxes
+    pub fn to_text(&self) -> String {
+        let mut result = String::new();
+        for row in self.rows() {
+            result.push_str(&row);
+            result.push('\n');
+        }
+        result
+    }
+
+    /// Returns the values of each row of the grid, with `Grid::EMPTY_CHAR` for the empty boxes
+    pub fn rows(&self) -> Vec<String> {
+        let compact = self.to_compact_string();
+        let chars: Vec<char> = compact.chars().collect();
+        chars.chunks(self.size.width() as usize).map(|row| row.iter().collect()).collect()
     }
 
     /// Returns the size of the grid
@@ -37,9 +99,14 @@ impl Grid {
         self.empty_values == 0
     }
 
-    /// Returns the number of empty boxes in the grid
-    pub fn empty_values(&self) -> u16 {
-        self.empty_values
+    /// Returns the maximum number of a value in a row or a column: half of its boxes when it has
+    /// an even number of them, one more than the other value otherwise
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - the number of boxes of the row or the column
+    pub fn max_per_line(length: u8) -> u8 {
+        length.div_ceil(2)
     }
 
     /// Returns whether or not a value must be put in the grid
@@ -54,8 +121,7 @@ impl Grid {
     ///
     /// Panics if `x_axis` or `y_axis` are greater than the size of the grid
     pub fn must_put(&self, x_axis: u8, y_axis: u8, value: Value) -> bool {
-        let size = self.size.as_u8();
-        assert!(x_axis < size && y_axis < size);
+        assert!(x_axis < self.size.height() && y_axis < self.size.width());
         let the_other_value = value.the_other();
         !self.can_put(x_axis, y_axis, the_other_value)
     }
@@ -72,10 +138,43 @@ impl Grid {
     ///
     /// Panics if `x_axis` or `y_axis` are greater than the size of the grid
     pub fn can_put(&self, x_axis: u8, y_axis: u8, value: Value) -> bool {
-        let size = self.size.as_u8();
-        assert!(x_axis < size && y_axis < size);
-        self.can_accept(Axis::X, x_axis, y_axis, value) &&
-            self.can_accept(Axis::Y, x_axis, y_axis, value)
+        !self.breaks_row(x_axis, y_axis, value) && !self.breaks_column(x_axis, y_axis, value)
+    }
+
+    /// Returns whether or not putting a value in the grid makes its row violate a constraint
+    ///
+    /// # Arguments
+    ///
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `value` - a `Value`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x_axis` or `y_axis` are greater than the size of the grid
+    pub fn breaks_row(&self, x_axis: u8, y_axis: u8, value: Value) -> bool {
+        assert!(x_axis < self.size.height() && y_axis < self.size.width());
+        let (width, height) = (self.size.width(), self.size.height());
+        !self.can_accept(&self.rows[x_axis as usize], width, y_axis, value) ||
+            self.violate_constraint_identical_rows_or_columns(&self.rows[..height as usize], width, x_axis, y_axis, value)
+    }
+
+    /// Returns whether or not putting a value in the grid makes its column violate a constraint
+    ///
+    /// # Arguments
+    ///
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `value` - a `Value`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x_axis` or `y_axis` are greater than the size of the grid
+    pub fn breaks_column(&self, x_axis: u8, y_axis: u8, value: Value) -> bool {
+        assert!(x_axis < self.size.height() && y_axis < self.size.width());
+        let (width, height) = (self.size.width(), self.size.height());
+        !self.can_accept(&self.columns[y_axis as usize], height, x_axis, value) ||
+            self.violate_constraint_identical_rows_or_columns(&self.columns[..width as usize], height, y_axis, x_axis, value)
     }
 
     /// Puts a value in the grid and returns the previous one
@@ -90,10 +189,10 @@ impl Grid {
     ///
     /// Panics if `x_axis` or `y_axis` are greater than the size of the grid
     pub fn put(&mut self, x_axis: u8, y_axis: u8, value: Option<Value>) -> Option<Value> {
-        let size = self.size.as_u8();
-        assert!(x_axis < size && y_axis < size);
+        assert!(x_axis < self.size.height() && y_axis < self.size.width());
         let result = self.get(x_axis, y_axis);
-        self.matrix[x_axis as usize][y_axis as usize] = value;
+        self.rows[x_axis as usize].put(y_axis, result, value);
+        self.columns[y_axis as usize].put(x_axis, result, value);
         if value.is_some() && result.is_none() {
             self.empty_values -= 1;
         } else if value.is_none() && result.is_some() {
@@ -113,69 +212,36 @@ impl Grid {
     ///
     /// Panics if `x_axis` or `y_axis` are greater than the size of the grid
     pub fn get(&self, x_axis: u8, y_axis: u8) -> Option<Value> {
-        let size = self.size.as_u8();
-        assert!(x_axis < size && y_axis < size);
-        self.matrix[x_axis as usize][y_axis as usize]
+        assert!(x_axis < self.size.height() && y_axis < self.size.width());
+        self.rows[x_axis as usize].get(y_axis)
     }
 
-    /// Returns whether or not the grid can accept a value in the nth row or column
+    /// Returns whether or not a row or a column can accept a value in its nth box
     ///
-    /// Arguments
+    /// # Arguments
     ///
-    /// * `axis` - the axis we are working on
-    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
-    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `line` - a row or a column
+    /// * `length` - the number of boxes of the row or the column
+    /// * `k` - the index of the box in the row or the column
     /// * `value` - a `Value`
-    fn can_accept(&self, axis: Axis, x_axis: u8, y_axis: u8, value: Value) -> bool {
-        let mut total_number: u8 = 0;
-        let mut adjacent_number: u8 = 0;
-
-        let index_in_changing_axis = match axis {
-            Axis::X => y_axis,
-            Axis::Y => x_axis,
-        };
-
-        for k in 0..self.size.as_u8() {
-            if k == index_in_changing_axis {
-                total_number += 1;
-                adjacent_number += 1;
-            } else {
-                let mut manage_numbers = |i: u8, j: u8| {
-                    let v = self.matrix[i as usize][j as usize];
-                    match v {
-                        Some(val) => {
-                            if val == value {
-                                total_number += 1;
-                                adjacent_number += 1;
-                            } else {
-                                adjacent_number = 0;
-                            }
-                        },
-                        None => adjacent_number = 0,
-                    }
-                };
-
-                match axis {
-                    Axis::X => manage_numbers(x_axis, k),
-                    Axis::Y => manage_numbers(k, y_axis),
-                };
-            }
-            if self.violate_constraint_max_per_row_or_column(total_number) ||
-                self.violate_constraint_max_adjacent_in_row_or_column(adjacent_number) {
-                return false;
-            }
-        }
-        true
+    fn can_accept(&self, line: &LineBits, length: u8, k: u8, value: Value) -> bool {
+        let bit: Mask = 1 << k;
+        let with_value = (line.mask_of(value) & !bit) | bit;
+        let total_number = with_value.count_ones() as u8;
+        let three_adjacent = with_value & (with_value >> 1) & (with_value >> 2);
+        !self.violate_constraint_max_per_row_or_column(total_number, length) &&
+            !self.violate_constraint_max_adjacent_in_row_or_column(three_adjacent)
     }
 
     /// Returns whether or not the grid violates the constraint saying a row or a column must
-    /// contain as much of a value as of the other
+    /// contain as much of a value as of the other, or one more when its length is odd
     ///
     /// # Arguments
     ///
     /// * `number` - the number of a value in a row or a column
-    fn violate_constraint_max_per_row_or_column(&self, number: u8) -> bool {
-        number > self.size.as_u8() / 2
+    /// * `length` - the number of boxes of the row or the column
+    fn violate_constraint_max_per_row_or_column(&self, number: u8, length: u8) -> bool {
+        number > Grid::max_per_line(length)
     }
 
     /// Returns whether or not the grid violates the constraint saying a row or a column cannot
@@ -183,30 +249,60 @@ impl Grid {
     ///
     /// # Arguments
     ///
-    /// * `number` - the number of the same value side by side in a row or a column
-    fn violate_constraint_max_adjacent_in_row_or_column(&self, number: u8) -> bool {
-        number > 2
+    /// * `three_adjacent` - the boxes of a row or a column starting three times the same value
+    fn violate_constraint_max_adjacent_in_row_or_column(&self, three_adjacent: Mask) -> bool {
+        three_adjacent != 0
     }
+
+    /// Returns whether or not putting a value in the grid violates the constraint saying two rows
+    /// or two columns cannot be identical
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - the rows or the columns of the grid
+    /// * `length` - the number of boxes of each row or column
+    /// * `index` - the index of the row or the column
+    /// * `k` - the index of the box in the row or the column
+    /// * `value` - a `Value`
+    fn violate_constraint_identical_rows_or_columns(&self, lines: &[LineBits], length: u8, index: u8, k: u8, value: Value) -> bool {
+        let bit: Mask = 1 << k;
+        let full: Mask = Mask::MAX >> (Mask::BITS - length as u32);
+        if lines[index as usize].filled | bit != full {
+            return false;
+        }
+        let seconds = match value {
+            Value::First => lines[index as usize].seconds & !bit,
+            Value::Second => lines[index as usize].seconds | bit,
+        };
+        lines.iter().enumerate()
+            .any(|(other_index, other)| other_index != index as usize && other.filled == full && other.seconds == seconds)
+    }
+
+    /// The maximum number of boxes of a row or a column
+    const MAX_SIDE: usize = Size::MAX_SIDE as usize;
+
+    /// The character representing an empty box
+    pub const EMPTY_CHAR: char = '.';
 }
 
 impl fmt::Display for Grid {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let display_line = || {
             let mut result = "\n".to_owned();
-            for _ in 0..self.size.as_u8() {
+            for _ in 0..self.size.width() {
                 result.push_str("----");
             }
-            result.push_str("-");
+            result.push('-');
             result
         };
 
         let display_cell = |i| {
             let mut result = "\n|".to_owned();
-            for j in 0..self.size.as_u8() {
-                result.push_str(" ");
-                match &self.matrix[i as usize][j as usize] {
+            for j in 0..self.size.width() {
+                result.push(' ');
+                match self.get(i, j) {
                     Some(n) => result.push_str(format!("{}", n).as_str()),
-                    None => result.push_str(" "),
+                    None => result.push(' '),
                 }
                 result.push_str(" |");
             }
@@ -214,7 +310,7 @@ impl fmt::Display for Grid {
         };
 
         let mut grid = "".to_owned();
-        for i in 0..self.size.as_u8() {
+        for i in 0..self.size.height() {
             grid.push_str(&display_line());
             grid.push_str(&display_cell(i));
         }
@@ -223,7 +319,62 @@ impl fmt::Display for Grid {
     }
 }
 
-enum Axis {
-    X,
-    Y,
+/// A row or a column of the grid
+#[derive(Clone, Copy, Default)]
+struct LineBits {
+    filled: Mask,
+    seconds: Mask,
+}
+
+impl LineBits {
+    /// Returns the value of the nth box
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - the index of the box
+    fn get(&self, k: u8) -> Option<Value> {
+        let bit: Mask = 1 << k;
+        if self.filled & bit == 0 {
+            None
+        } else if self.seconds & bit == 0 {
+            Some(Value::First)
+        } else {
+            Some(Value::Second)
+        }
+    }
+
+    /// Replaces the value of the nth box
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - the index of the box
+    /// * `old_value` - the previous value of the box
+    /// * `value` - the new value of the box
+    fn put(&mut self, k: u8, old_value: Option<Value>, value: Option<Value>) {
+        let bit: Mask = 1 << k;
+        if old_value.is_some() {
+            self.filled &= !bit;
+            self.seconds &= !bit;
+        }
+        match value {
+            Some(Value::First) => self.filled |= bit,
+            Some(Value::Second) => {
+                self.filled |= bit;
+                self.seconds |= bit;
+            },
+            None => {},
+        }
+    }
+
+    /// Returns the boxes containing a value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - a `Value`
+    fn mask_of(&self, value: Value) -> Mask {
+        match value {
+            Value::First => self.filled & !self.seconds,
+            Value::Second => self.seconds,
+        }
+    }
 }
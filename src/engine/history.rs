@@ -1,14 +1,17 @@
 //! # History
 //!
-//! `history` manages the history of the game
+//! `history` manages the history of the game, kept as a tree so that undoing actions and doing
+//! other ones does not lose the undone actions
 
-use std::fmt;
-use crate::enums::Value;
+use std::{collections::HashMap, fmt};
+use crate::enums::{Note, Value};
 
 /// The history of the game is represented here
 pub struct History {
     items: Vec<Item>,
     choices: Vec<usize>,
+    checkpoints: Vec<Checkpoint>,
+    latest_children: HashMap<Option<usize>, usize>,
     current_item: Option<usize>,
 }
 
@@ -18,11 +21,118 @@ impl History {
         History {
             items: Vec::new(),
             choices: Vec::new(),
+            checkpoints: Vec::new(),
+            latest_children: HashMap::new(),
             current_item: None,
         }
     }
 
-    /// Pushes an action in the history
+    /// Returns a history restored from a string made by `History::to_compact_string` or `None` if
+    /// that string is not valid
+    ///
+    /// # Arguments
+    ///
+    /// * `compact` - a string slice representing a history
+    pub fn from_compact_str(compact: &str) -> Option<History> {
+        let (current, items) = compact.split_once(History::CURRENT_SEPARATOR)?;
+        let (items, checkpoints) = items.split_once(History::CURRENT_SEPARATOR)?;
+        let mut result = History::new();
+        let index = |field: &str| -> Option<Option<usize>> {
+            match field {
+                History::ROOT => Some(None),
+                _ => field.parse().ok().map(Some),
+            }
+        };
+        if !items.is_empty() {
+            for (i, item) in items.split(History::ITEM_SEPARATOR).enumerate() {
+                let is_a_choice = item.ends_with(History::CHOICE_MARK);
+                let fields: Vec<&str> = item.trim_end_matches(History::CHOICE_MARK).split(',').collect();
+                if fields.len() != 7 {
+                    return None;
+                }
+                let parent = index(fields[6])?;
+                if parent.is_some_and(|parent| parent >= i) {
+                    return None;
+                }
+                let value = |field: &str| -> Option<Option<Value>> {
+                    match field.chars().next() {
+                        Some(History::EMPTY_CHAR) => Some(None),
+                        Some(c) => Value::from_char(c).map(Some),
+                        None => None,
+                    }
+                };
+                let note = |field: &str| -> Option<Option<Note>> {
+                    match field.chars().next() {
+                        Some(History::EMPTY_CHAR) => Some(None),
+                        Some(c) => Note::from_char(c).map(Some),
+                        None => None,
+                    }
+                };
+                let x_axis = fields[0].parse().ok()?;
+                let y_axis = fields[1].parse().ok()?;
+                result.items.push(Item::new(x_axis, y_axis, value(fields[2])?, value(fields[3])?, note(fields[4])?, note(fields[5])?, parent));
+                if is_a_choice {
+                    result.choices.push(i);
+                }
+            }
+        }
+        if !checkpoints.is_empty() {
+            for checkpoint in checkpoints.split(History::ITEM_SEPARATOR) {
+                let (item, name) = checkpoint.split_once(History::NAME_SEPARATOR)?;
+                let item = index(item)?;
+                if item.is_some_and(|item| item >= result.items.len()) {
+                    return None;
+                }
+                result.checkpoints.push(Checkpoint::new(name, item));
+            }
+        }
+        result.current_item = match current.parse::<usize>() {
+            Ok(curr) if curr < result.items.len() => Some(curr),
+            Ok(_) => return None,
+            Err(_) => None,
+        };
+        Some(result)
+    }
+
+    /// Returns the history as a string that can be restored with `History::from_compact_str`
+    pub fn to_compact_string(&self) -> String {
+        let value_as_str = |value: Option<Value>| -> String {
+            match value {
+                Some(val) => format!("{}", val),
+                None => History::EMPTY_CHAR.to_string(),
+            }
+        };
+        let note_as_str = |note: Option<Note>| -> String {
+            match note {
+                Some(note) => format!("{}", note),
+                None => History::EMPTY_CHAR.to_string(),
+            }
+        };
+        let index_as_str = |index: Option<usize>| -> String {
+            match index {
+                Some(index) => format!("{}", index),
+                None => History::ROOT.to_owned(),
+            }
+        };
+
+        let items: Vec<String> = self.items.iter().enumerate().map(|(i, item)| {
+            let mut result = format!("{},{},{},{},{},{},{}", item.x_axis, item.y_axis, value_as_str(item.old_value), value_as_str(item.new_value),
+                                     note_as_str(item.old_note), note_as_str(item.new_note), index_as_str(item.parent));
+            if self.choices.contains(&i) {
+                result.push(History::CHOICE_MARK);
+            }
+            result
+        }).collect();
+        let checkpoints: Vec<String> = self.checkpoints.iter()
+            .map(|checkpoint| format!("{}{}{}", index_as_str(checkpoint.item), History::NAME_SEPARATOR, checkpoint.name))
+            .collect();
+        let current = index_as_str(self.current_item);
+        format!("{}{}{}{}{}", current, History::CURRENT_SEPARATOR, items.join(&History::ITEM_SEPARATOR.to_string()),
+                History::CURRENT_SEPARATOR, checkpoints.join(&History::ITEM_SEPARATOR.to_string()))
+    }
+
+    /// Pushes an action on a value in the history, as a new branch if the current item already
+    /// has a following one
     ///
     /// # Arguments
     ///
@@ -30,21 +140,36 @@ impl History {
     /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
     /// * `old_value` - the previous value
     /// * `new value` - the new value
+    /// * `note` - the note of the box, left unchanged
     /// * `is_a_choice` - whether or not the new value is a choice
-    pub fn push(&mut self, x_axis: u8, y_axis: u8, old_value: Option<Value>, new_value: Option<Value>, is_a_choice: bool) {
-        self.pop_all_after_current_item();
-        let next_item = self.items.len();
-        self.current_item = Some(next_item);
-        if is_a_choice {
-            self.choices.push(next_item);
-        }
-        let item = Item::new(x_axis, y_axis, old_value, new_value);
-        self.items.push(item);
+    pub fn push(&mut self, x_axis: u8, y_axis: u8, old_value: Option<Value>, new_value: Option<Value>, note: Option<Note>, is_a_choice: bool) {
+        self.push_item(Item::new(x_axis, y_axis, old_value, new_value, note, note, self.current_item), is_a_choice);
     }
 
-    /// Returns the index of the latest choice in the history
+    /// Pushes an action on a note in the history, as a new branch if the current item already
+    /// has a following one
+    ///
+    /// # Arguments
+    ///
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `value` - the value of the box, left unchanged
+    /// * `old_note` - the previous note
+    /// * `new_note` - the new note
+    pub fn push_note(&mut self, x_axis: u8, y_axis: u8, value: Option<Value>, old_note: Option<Note>, new_note: Option<Note>) {
+        self.push_item(Item::new(x_axis, y_axis, value, value, old_note, new_note, self.current_item), false);
+    }
+
+    /// Removes the latest choice made before the current item and returns its index
     pub fn latest_choice(&mut self) -> Option<usize> {
-        self.choices.pop()
+        let mut current = self.current_item;
+        while let Some(index) = current {
+            if let Some(position) = self.choices.iter().position(|&choice| choice == index) {
+                return Some(self.choices.remove(position));
+            }
+            current = self.items[index].parent;
+        }
+        None
     }
 
     /// Returns the index of the current item of the history
@@ -69,15 +194,14 @@ impl History {
     /// Panics if no possible undo
     pub fn undo(&mut self) -> &Item {
         let curr = self.current_item.unwrap();
-        if curr == 0 {
-            self.current_item = None;
-        } else {
-            self.current_item = Some(curr - 1);
-        }
+        let parent = self.items[curr].parent;
+        self.latest_children.insert(parent, curr);
+        self.current_item = parent;
         self.items.get(curr).unwrap()
     }
 
-    /// Replays the next action that was previously undone and returns it
+    /// Replays the next action that was previously undone and returns it, following the branch
+    /// visited last
     ///
     /// # Panics
     ///
@@ -88,63 +212,197 @@ impl History {
         self.items.get(next).unwrap()
     }
 
+    /// Returns the number of actions to undo and the indexes of the actions to redo, in that
+    /// order, to go from the current item to another one
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - the index of an item or `None` for the beginning of the game
+    pub fn path_to(&self, item: Option<usize>) -> (usize, Vec<usize>) {
+        let from = self.ancestors(self.current_item);
+        let mut to = self.ancestors(item);
+        let common = to.iter().position(|index| from.contains(index)).unwrap_or(to.len());
+        let undos = from.iter().position(|index| to.get(common) == Some(index)).unwrap_or(from.len());
+        to.truncate(common);
+        to.reverse();
+        (undos, to)
+    }
+
+    /// Chooses the branch followed by the next redo
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - the index of an item following the current one
+    ///
+    /// # Panics
+    ///
+    /// Panics if the item does not follow the current one
+    pub fn follow(&mut self, item: usize) {
+        assert_eq!(self.items[item].parent, self.current_item);
+        self.latest_children.insert(self.current_item, item);
+    }
+
+    /// Forgets an action and all those pushed after it when they all follow it, as when a choice
+    /// of the solver proves to be wrong
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - the index of an item that is neither the current one nor one of its ancestors
+    pub fn forget(&mut self, item: usize) {
+        let in_branch = self.items[item + 1..].iter().all(|next| next.parent.is_some_and(|parent| parent >= item));
+        if in_branch && !self.checkpoints.iter().any(|checkpoint| checkpoint.item.is_some_and(|index| index >= item)) {
+            self.items.truncate(item);
+            self.choices.retain(|&choice| choice < item);
+            self.latest_children.retain(|_, &mut child| child < item);
+        }
+    }
+
+    /// Sets a checkpoint on the current item
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the checkpoint, without the characters used to separate the parts
+    ///   of the history
+    pub fn add_checkpoint(&mut self, name: &str) {
+        let name = name.replace([History::CURRENT_SEPARATOR, History::ITEM_SEPARATOR], " ");
+        self.checkpoints.push(Checkpoint::new(name.trim(), self.current_item));
+    }
+
+    /// Returns the checkpoints, from the oldest to the latest one
+    pub fn checkpoints(&self) -> &[Checkpoint] {
+        &self.checkpoints
+    }
+
+    /// Returns the actions of the history
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// Returns whether or not an action is a choice
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - the index of an item
+    pub fn is_a_choice(&self, item: usize) -> bool {
+        self.choices.contains(&item)
+    }
+
+    /// Returns the indexes of the actions in the order of the branches, each with the number of
+    /// branchings leading to it
+    pub fn branches(&self) -> Vec<(usize, usize)> {
+        let mut children: HashMap<Option<usize>, Vec<usize>> = HashMap::new();
+        for (i, item) in self.items.iter().enumerate() {
+            children.entry(item.parent).or_default().push(i);
+        }
+        let mut result = Vec::new();
+        let mut pending: Vec<(Option<usize>, usize)> = vec![(None, 0)];
+        while let Some((parent, depth)) = pending.pop() {
+            if let Some(children) = children.get(&parent) {
+                for (k, &child) in children.iter().enumerate().rev() {
+                    pending.push((Some(child), depth + usize::from(k > 0)));
+                }
+            }
+            if let Some(parent) = parent {
+                result.push((parent, depth));
+            }
+        }
+        result
+    }
+
     /// Clears the history
     pub fn clear(&mut self) {
+        self.items.clear();
+        self.choices.clear();
+        self.checkpoints.clear();
+        self.latest_children.clear();
         self.current_item = None;
-        self.pop_all_after_current_item();
     }
 
-    /// Returns the next item of the history if it exists
+    /// Pushes an item in the history, after the current one
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - an item of the history
+    /// * `is_a_choice` - whether or not the new value of the item is a choice
+    fn push_item(&mut self, item: Item, is_a_choice: bool) {
+        let next_item = self.items.len();
+        self.latest_children.insert(self.current_item, next_item);
+        self.current_item = Some(next_item);
+        if is_a_choice {
+            self.choices.push(next_item);
+        }
+        self.items.push(item);
+    }
+
+    /// Returns the next item of the history if it exists: the one visited last or else the latest
+    /// one following the current item
     fn next_item(&self) -> Option<usize> {
-        let current = self.current_item;
-        let next = match current {
-            Some(curr) => curr + 1,
-            None => 0,
-        };
-        if self.items.get(next).is_none() {
-            None
-        } else {
-            Some(next)
+        match self.latest_children.get(&self.current_item) {
+            Some(&next) => Some(next),
+            None => self.items.iter().rposition(|item| item.parent == self.current_item),
         }
     }
 
-    /// Clears the history after the current item
-    fn pop_all_after_current_item(&mut self) {
-        if let Some(next) = self.next_item() {
-            let size = self.items.len();
-            for i in next..size {
-                self.items.pop();
-                self.choices.retain(|&item| item != i);
-            }
+    /// Returns an item and its ancestors, from that item to the first action of the game
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - the index of an item or `None` for the beginning of the game
+    fn ancestors(&self, item: Option<usize>) -> Vec<usize> {
+        let mut result = Vec::new();
+        let mut current = item;
+        while let Some(index) = current {
+            result.push(index);
+            current = self.items[index].parent;
         }
+        result
+    }
+
+    const CURRENT_SEPARATOR: char = '|';
+    const ITEM_SEPARATOR: char = ';';
+    const NAME_SEPARATOR: char = '=';
+    const CHOICE_MARK: char = '*';
+    const EMPTY_CHAR: char = '.';
+    const ROOT: &'static str = "-";
+}
+
+impl Default for History {
+    fn default() -> History {
+        History::new()
     }
 }
 
 impl fmt::Display for History {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut history = "\n".to_owned();
-        for i in 0..self.items.len() {
+        for (i, depth) in self.branches() {
+            history.push_str(&"  ".repeat(depth));
             history.push_str(&format!("{}", self.items.get(i).unwrap()));
             if self.choices.contains(&i) {
                 history.push_str(" *");
             }
-            if let Some(current) = self.current_item {
-                if current == i {
-                    history.push_str(" <-");
-                }
+            for checkpoint in self.checkpoints.iter().filter(|checkpoint| checkpoint.item == Some(i)) {
+                history.push_str(&format!(" [{}]", checkpoint.name));
+            }
+            if self.current_item == Some(i) {
+                history.push_str(" <-");
             }
-            history.push_str("\n");
+            history.push('\n');
         }
         write!(f, "{}", history)
     }
 }
 
 /// An item of the history of the game is represented here
+#[derive(Clone, Copy)]
 pub struct Item {
     x_axis: u8,
     y_axis: u8,
     old_value: Option<Value>,
     new_value: Option<Value>,
+    old_note: Option<Note>,
+    new_note: Option<Note>,
+    parent: Option<usize>,
 }
 
 impl Item {
@@ -156,12 +414,18 @@ impl Item {
     /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
     /// * `old_value` - the previous value
     /// * `new value` - the new value
-    fn new(x_axis: u8, y_axis: u8, old_value: Option<Value>, new_value: Option<Value>) -> Item {
+    /// * `old_note` - the previous note
+    /// * `new_note` - the new note
+    /// * `parent` - the index of the item it follows or `None` if it is a first action
+    fn new(x_axis: u8, y_axis: u8, old_value: Option<Value>, new_value: Option<Value>, old_note: Option<Note>, new_note: Option<Note>, parent: Option<usize>) -> Item {
         Item {
             x_axis,
             y_axis,
             old_value,
             new_value,
+            old_note,
+            new_note,
+            parent,
         }
     }
 
@@ -184,6 +448,22 @@ impl Item {
     pub fn new_value(&self) -> Option<Value> {
         self.new_value
     }
+
+    /// Returns the previous note of an item of the history
+    pub fn old_note(&self) -> Option<Note> {
+        self.old_note
+    }
+
+    /// Returns the new note of an item of the history
+    pub fn new_note(&self) -> Option<Note> {
+        self.new_note
+    }
+
+    /// Returns the index of the item followed by an item of the history or `None` if it is a
+    /// first action
+    pub fn parent(&self) -> Option<usize> {
+        self.parent
+    }
 }
 
 impl fmt::Display for Item {
@@ -194,8 +474,158 @@ impl fmt::Display for Item {
                 None => "''".to_owned(),
             }
         };
+        let note_as_str = |note: Option<Note>| -> String {
+            match note {
+                Some(note) => format!("{}", note),
+                None => "''".to_owned(),
+            }
+        };
+
+        write!(f, "x-axis: {}, y-axis: {}, old value: {}, new value: {}, old note: {}, new note: {}", self.x_axis, self.y_axis,
+               value_as_str(self.old_value), value_as_str(self.new_value), note_as_str(self.old_note), note_as_str(self.new_note))
+    }
+}
+
+/// A checkpoint set by the player on an item of the history is represented here
+pub struct Checkpoint {
+    name: String,
+    item: Option<usize>,
+}
+
+impl Checkpoint {
+    /// Returns a new checkpoint
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the checkpoint
+    /// * `item` - the index of the item or `None` for the beginning of the game
+    fn new(name: &str, item: Option<usize>) -> Checkpoint {
+        Checkpoint {
+            name: name.to_owned(),
+            item,
+        }
+    }
+
+    /// Returns the name of the checkpoint
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the index of the item of the checkpoint or `None` for the beginning of the game
+    pub fn item(&self) -> Option<usize> {
+        self.item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{Binero, grid::Grid};
+    use crate::enums::Size;
+
+    /// Returns a history with two branches starting from the beginning of the game, a note, a
+    /// choice and a checkpoint
+    fn branched_history() -> History {
+        let mut history = History::new();
+        history.push(0, 0, None, Some(Value::First), None, false);
+        history.undo();
+        history.push(0, 0, None, Some(Value::Second), None, false);
+        history.push_note(0, 1, None, None, Some(Note::Marked));
+        history.add_checkpoint("before the choice");
+        history.push(1, 1, None, Some(Value::First), Some(Note::Marked), true);
+        history
+    }
+
+    #[test]
+    fn compact_string_round_trip() {
+        let history = branched_history();
+        let compact = history.to_compact_string();
+        let restored = History::from_compact_str(&compact).unwrap();
+        assert_eq!(restored.to_compact_string(), compact);
+        assert_eq!(restored.current_item(), Some(3));
+        assert_eq!(restored.items()[1].parent(), None);
+        assert_eq!(restored.items()[3].parent(), Some(2));
+        assert!(restored.items()[2].new_note() == Some(Note::Marked));
+        assert!(restored.is_a_choice(3));
+        assert_eq!(restored.checkpoints()[0].name(), "before the choice");
+        assert_eq!(restored.checkpoints()[0].item(), Some(2));
+    }
+
+    #[test]
+    fn invalid_compact_strings() {
+        assert!(History::from_compact_str("").is_none());
+        assert!(History::from_compact_str("0|0,0,.,0,.,.,-").is_none());
+        assert!(History::from_compact_str("0|0,0,.,0,.,.,0|").is_none());
+        assert!(History::from_compact_str("0|0,0,.,0|").is_none());
+        assert!(History::from_compact_str("1|0,0,.,0,.,.,-|").is_none());
+        assert!(History::from_compact_str("-|0,0,.,2,.,.,-|").is_none());
+        assert!(History::from_compact_str("-|0,0,.,0,.,.,-|1=far").is_none());
+        assert!(History::from_compact_str("0|0,0,.,0,.,.,-|").is_some());
+    }
+
+    #[test]
+    fn path_between_sibling_branches() {
+        let mut history = branched_history();
+        assert_eq!(history.path_to(Some(0)), (3, vec![0]));
+        assert_eq!(history.path_to(Some(1)), (2, vec![]));
+        assert_eq!(history.path_to(None), (3, vec![]));
+        assert_eq!(history.path_to(Some(3)), (0, vec![]));
+        history.undo();
+        history.undo();
+        history.undo();
+        assert_eq!(history.path_to(Some(3)), (0, vec![1, 2, 3]));
+        history.redo();
+        assert_eq!(history.current_item(), Some(1));
+        assert_eq!(history.path_to(Some(0)), (1, vec![0]));
+    }
+
+    #[test]
+    fn forget_a_branch() {
+        let mut history = branched_history();
+        history.undo();
+        history.add_checkpoint("after the note");
+        history.forget(3);
+        assert_eq!(history.items().len(), 3);
+        assert!(!history.is_a_choice(3));
+        assert!(!history.is_redo_possible());
+    }
+
+    #[test]
+    fn forget_a_branch_protected_by_a_checkpoint() {
+        let mut history = branched_history();
+        history.add_checkpoint("after the choice");
+        history.undo();
+        history.forget(3);
+        assert_eq!(history.items().len(), 4);
+        assert!(history.is_a_choice(3));
+        assert!(history.is_redo_possible());
+    }
 
-        write!(f, "x-axis: {}, y-axis: {}, old value: {}, new value: {}", self.x_axis, self.y_axis,
-               value_as_str(self.old_value), value_as_str(self.new_value))
+    #[test]
+    fn backtrack_on_a_branched_history() {
+        let mut binero = Binero::from_grid(&Grid::new(Size::new(6, 6).unwrap()));
+        assert!(binero.try_to_put(0, 0, Some(Value::First)));
+        binero.try_to_undo();
+        assert!(binero.try_to_put(0, 0, Some(Value::Second)));
+        binero.put_a_choice(0, 1, Some(Value::First));
+        binero.put_a_mandatory_value(0, 2, Value::Second);
+        assert!(binero.backtrack_to_latest_choice());
+        assert!(binero.get(0, 1) == Some(Value::Second));
+        assert!(binero.get(0, 2).is_none());
+        let history = binero.history();
+        assert_eq!(history.items().len(), 3);
+        assert_eq!(history.current_item(), Some(2));
+        assert_eq!(history.items()[2].parent(), Some(1));
+        assert!(!history.is_a_choice(2));
+        assert_eq!(history.path_to(Some(0)), (2, vec![0]));
+        assert!(binero.backtrack_to_latest_choice());
+        assert!(binero.get(0, 0) == Some(Value::First));
+        assert!(binero.get(0, 1).is_none());
+        let history = binero.history();
+        assert_eq!(history.items().len(), 2);
+        assert_eq!(history.items()[1].parent(), None);
+        assert!(history.is_a_choice(0));
+        assert!(!history.is_a_choice(1));
+        assert!(!binero.backtrack_to_latest_choice());
     }
 }
@@ -2,66 +2,145 @@
 //!
 //! `engine` contains the functions that create and solve a binero
 
-mod grid;
+pub mod export;
+pub mod grid;
 pub mod history;
+pub mod notes;
+pub mod pack;
+pub mod solver;
 
-use std::fmt;
+use std::{fmt, ops::RangeInclusive, str::FromStr, sync::atomic::{AtomicBool, Ordering}};
 use rand::{Rng, prelude::*};
+use rand_chacha::ChaCha8Rng;
 use grid::Grid;
 use history::{History, Item};
-use crate::enums::{Difficulty, Size, Value};
+use notes::Notes;
+use solver::{Rating, Solver, Step, Technique};
+use enum_iterator::all;
+use crate::enums::{Difficulty, Note, Size, Value};
 
 /// A binero game is represented here
 pub struct Binero {
+    seed: Option<u64>,
+    rng: ChaCha8Rng,
+    difficulty: Difficulty,
     grid: Grid,
+    givens: Grid,
+    solution: Grid,
+    notes: Notes,
     history: History,
+    rating: Rating,
 }
 
 impl Binero {
     /// Returns a binero, with the given size, ready to be played
     ///
+    /// The rating of the binero always matches the difficulty, other solutions being generated
+    /// until one does. The same seed, size and difficulty always give the same binero, on every
+    /// platform and build, the random generator being ChaCha8.
+    ///
     /// # Arguments
     ///
     /// * `size` - a size
     /// * `difficulty` - a level of difficulty
-    pub fn new(size: Size, difficulty: Difficulty) -> Binero {
+    /// * `seed` - the seed of the random generator or `None` to pick one randomly
+    pub fn new(size: Size, difficulty: Difficulty, seed: Option<u64>) -> Binero {
+        Binero::new_cancellable(size, difficulty, seed, &AtomicBool::new(false)).unwrap()
+    }
+
+    /// Returns a binero, with the given size, ready to be played or `None` if the generation was
+    /// cancelled by setting `cancelled`, which is checked between two attempts and two removed values
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - a size
+    /// * `difficulty` - a level of difficulty
+    /// * `seed` - the seed of the random generator or `None` to pick one randomly
+    /// * `cancelled` - a flag set by another thread to stop the generation
+    pub fn new_cancellable(size: Size, difficulty: Difficulty, seed: Option<u64>, cancelled: &AtomicBool) -> Option<Binero> {
+        let seed = seed.unwrap_or_else(|| rand::rng().random());
         let mut result = Binero {
+            seed: Some(seed),
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            difficulty,
             grid: Grid::new(size),
+            givens: Grid::new(size),
+            solution: Grid::new(size),
+            notes: Notes::new(size),
             history: History::new(),
+            rating: Rating::default(),
         };
-        result.try_to_solve();
-        result.history.clear();
-        result.make_playable(difficulty);
-        result
-    }
-
-    /// Try to solve a binero and returns if it could or not
-    pub fn try_to_solve(&mut self) -> bool {
-        let mut grid_can_be_solved = true;
-        while !self.grid.is_full() {
-            loop {
-                let (some_value_put, backtrack_impossible) = self.put_mandatory_values();
-                if backtrack_impossible {
-                    grid_can_be_solved = false;
-                    break;
-                }
-                if !some_value_put {
-                    break;
+        loop {
+            while !result.try_to_solve_within(Binero::MAX_CHOICES) {
+                if cancelled.load(Ordering::Relaxed) {
+                    return None;
                 }
+                result.grid = Grid::new(size);
+                result.history.clear();
             }
-            if !grid_can_be_solved {
+            result.history.clear();
+            result.solution = result.grid.clone();
+            if result.make_playable(difficulty, cancelled) {
                 break;
             }
-            if !self.grid.is_full() {
-                if !self.try_a_choice() {
-                    grid_can_be_solved = self.backtrack_to_latest_choice();
-                    if !grid_can_be_solved {
-                        break;
-                    }
-                }
+            if cancelled.load(Ordering::Relaxed) {
+                return None;
             }
+            result.grid = Grid::new(size);
         }
-        grid_can_be_solved
+        result.givens = result.grid.clone();
+        Some(result)
+    }
+
+    /// Returns a binero restored from the state of a game, or `None` if that state is not valid
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - a size
+    /// * `difficulty` - a level of difficulty
+    /// * `seed` - the seed the binero was generated with or `None` if it was imported
+    /// * `givens` - the values given at the beginning of the game, as made by `Grid::to_compact_string`
+    /// * `grid` - the current values, as made by `Grid::to_compact_string`
+    /// * `solution` - the solution, as made by `Grid::to_compact_string`
+    /// * `history` - the history of the game, as made by `History::to_compact_string`
+    pub fn restore(size: Size, difficulty: Difficulty, seed: Option<u64>, givens: &str, grid: &str, solution: &str, history: &str) -> Option<Binero> {
+        let givens = Grid::from_compact_str(size, givens)?;
+        let solution = Grid::from_compact_str(size, solution)?;
+        if !Binero::is_solution_of(&solution, &givens) {
+            return None;
+        }
+        let rating = Solver::new(&givens).rate(&solution);
+        let result = Binero {
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed.unwrap_or_default()),
+            difficulty,
+            grid: Grid::from_compact_str(size, grid)?,
+            givens,
+            solution,
+            notes: Notes::new(size),
+            history: History::from_compact_str(history)?,
+            rating,
+        };
+        if result.is_consistent() {
+            Some(result)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the binero with the notes of the player restored, or `None` if they are not valid
+    ///
+    /// # Arguments
+    ///
+    /// * `notes` - the notes of the player, as made by `Notes::to_compact_string`
+    pub fn with_notes(mut self, notes: &str) -> Option<Binero> {
+        self.notes = Notes::from_compact_str(self.size(), notes)?;
+        Some(self)
+    }
+
+    /// Try to solve a binero and returns if it could or not
+    pub fn try_to_solve(&mut self) -> bool {
+        self.try_to_solve_within(u32::MAX)
     }
 
     /// Try to put a value in the grid and returns whether or not it was possible
@@ -94,6 +173,7 @@ impl Binero {
         if self.history.is_undo_possible() {
             let item = self.history.undo();
             self.grid.put(item.x_axis(), item.y_axis(), item.old_value());
+            self.notes.put(item.x_axis(), item.y_axis(), item.old_note());
             Some(item)
         } else {
             None
@@ -105,12 +185,80 @@ impl Binero {
         if self.history.is_redo_possible() {
             let item = self.history.redo();
             self.grid.put(item.x_axis(), item.y_axis(), item.new_value());
+            self.notes.put(item.x_axis(), item.y_axis(), item.new_note());
             Some(item)
         } else {
             None
         }
     }
 
+    /// Writes a note in a box without a value, or removes it with `None`, and returns whether or
+    /// not it was possible
+    ///
+    /// # Arguments
+    ///
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `note` - an `Option<Note>`
+    pub fn try_to_note(&mut self, x_axis: u8, y_axis: u8, note: Option<Note>) -> bool {
+        let value = self.grid.get(x_axis, y_axis);
+        if value.is_some() || self.is_given(x_axis, y_axis) {
+            return false;
+        }
+        let old_note = self.notes.put(x_axis, y_axis, note);
+        if old_note != note {
+            self.history.push_note(x_axis, y_axis, value, old_note, note);
+        }
+        true
+    }
+
+    /// Returns the note written by the player in a box
+    ///
+    /// # Arguments
+    ///
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    pub fn note(&self, x_axis: u8, y_axis: u8) -> Option<Note> {
+        self.notes.get(x_axis, y_axis)
+    }
+
+    /// Sets a checkpoint on the current state of the game, for instance before a guess
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the checkpoint
+    pub fn add_checkpoint(&mut self, name: &str) {
+        self.history.add_checkpoint(name);
+    }
+
+    /// Goes back to a checkpoint, through the branches of the history, and returns the actions
+    /// undone then redone to reach it, each with whether or not it was undone
+    ///
+    /// # Arguments
+    ///
+    /// * `checkpoint` - the index of a checkpoint in `History::checkpoints`
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no such checkpoint
+    pub fn roll_back_to(&mut self, checkpoint: usize) -> Vec<(Item, bool)> {
+        let item = self.history.checkpoints()[checkpoint].item();
+        let (undos, redos) = self.history.path_to(item);
+        let mut result = Vec::new();
+        for _ in 0..undos {
+            if let Some(&item) = self.try_to_undo() {
+                result.push((item, true));
+            }
+        }
+        for next in redos {
+            self.history.follow(next);
+            if let Some(&item) = self.try_to_redo() {
+                result.push((item, false));
+            }
+        }
+        result
+    }
+
     /// Clears the history of the game
     pub fn clear_history(&mut self) {
         self.history.clear();
@@ -131,11 +279,136 @@ impl Binero {
         self.grid.get(x_axis, y_axis)
     }
 
+    /// Returns whether or not a value was given at the beginning of the game
+    ///
+    /// # Arguments
+    ///
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    pub fn is_given(&self, x_axis: u8, y_axis: u8) -> bool {
+        self.givens.get(x_axis, y_axis).is_some()
+    }
+
+    /// Returns whether or not a value put by the player differs from the solution
+    ///
+    /// # Arguments
+    ///
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    pub fn is_wrong(&self, x_axis: u8, y_axis: u8) -> bool {
+        let value = self.grid.get(x_axis, y_axis);
+        value.is_some() && value != self.solution.get(x_axis, y_axis)
+    }
+
+    /// Returns the x-axis and the y-axis of every value put by the player that differs from the
+    /// solution
+    pub fn errors(&self) -> Vec<(u8, u8)> {
+        let size = self.grid.size();
+        let mut result = Vec::new();
+        for i in 0..size.height() {
+            for j in 0..size.width() {
+                if self.is_wrong(i, j) {
+                    result.push((i, j));
+                }
+            }
+        }
+        result
+    }
+
     /// Returns the size of the grid
     pub fn size(&self) -> Size {
         self.grid.size()
     }
 
+    /// Returns the seed the binero was generated with or `None` if it was imported
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Returns the level of difficulty the binero was created with
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Returns the current grid
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Returns the grid with the values given at the beginning of the game
+    pub fn givens(&self) -> &Grid {
+        &self.givens
+    }
+
+    /// Returns the solution of the binero
+    pub fn solution(&self) -> &Grid {
+        &self.solution
+    }
+
+    /// Returns the notes written by the player
+    pub fn notes(&self) -> &Notes {
+        &self.notes
+    }
+
+    /// Returns the history of the game
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    /// Returns the rating of the binero, computed when it was created
+    pub fn rating(&self) -> Rating {
+        self.rating
+    }
+
+    /// Returns the next value that can be deduced from the current grid, if any
+    pub fn hint(&self) -> Option<Step> {
+        self.solver().next_step()
+    }
+
+    /// Returns a solver deducing values step by step from the current grid
+    pub fn solver(&self) -> Solver {
+        Solver::new(&self.grid)
+    }
+
+    /// Try to solve a binero making at most a number of choices and returns if it could or not
+    ///
+    /// # Arguments
+    ///
+    /// * `max_choices` - the maximum number of choices that can be made
+    fn try_to_solve_within(&mut self, max_choices: u32) -> bool {
+        let mut grid_can_be_solved = true;
+        let mut choices = 0;
+        while !self.grid.is_full() {
+            loop {
+                let (some_value_put, backtrack_impossible) = self.put_mandatory_values();
+                if backtrack_impossible {
+                    grid_can_be_solved = false;
+                    break;
+                }
+                if !some_value_put {
+                    break;
+                }
+            }
+            if !grid_can_be_solved {
+                break;
+            }
+            if !self.grid.is_full() {
+                if choices == max_choices {
+                    grid_can_be_solved = false;
+                    break;
+                }
+                choices += 1;
+                if !self.try_a_choice() {
+                    grid_can_be_solved = self.backtrack_to_latest_choice();
+                    if !grid_can_be_solved {
+                        break;
+                    }
+                }
+            }
+        }
+        grid_can_be_solved
+    }
+
     /// Put a choice in the grid
     ///
     /// # Arguments
@@ -145,7 +418,7 @@ impl Binero {
     /// * `value` - an `Option<Value>`
     fn put_a_choice(&mut self, x_axis: u8, y_axis: u8, value: Option<Value>) {
         let old_value = self.grid.put(x_axis, y_axis, value);
-        self.history.push(x_axis, y_axis, old_value, value, true);
+        self.history.push(x_axis, y_axis, old_value, value, self.notes.get(x_axis, y_axis), true);
     }
 
     /// Put a mandatory value in the grid
@@ -158,7 +431,7 @@ impl Binero {
     fn put_a_mandatory_value(&mut self, x_axis: u8, y_axis: u8, value: Value) {
         let new_value = Some(value);
         let old_value = self.grid.put(x_axis, y_axis, new_value);
-        self.history.push(x_axis, y_axis, old_value, new_value, false);
+        self.history.push(x_axis, y_axis, old_value, new_value, self.notes.get(x_axis, y_axis), false);
     }
 
     /// Returns the mandatory value for a place in the grid if it exists or `None` if there is no
@@ -186,18 +459,16 @@ impl Binero {
     /// added and whether or not backtracking was impossible
     fn put_mandatory_values(&mut self) -> (bool, bool) {
         let mut some_value_put = false;
-        for i in 0..self.grid.size().as_u8() {
-            for j in 0..self.grid.size().as_u8() {
-                if self.grid.get(i, j).is_none() {
-                    if let Some(value) = self.mandatory_value(i, j) {
-                        if self.grid.can_put(i, j, value) {
-                            self.put_a_mandatory_value(i, j, value);
-                        } else {
-                            let result = self.backtrack_to_latest_choice();
-                            return (result, !result);
-                        }
-                        some_value_put = true;
+        for i in 0..self.grid.size().height() {
+            for j in 0..self.grid.size().width() {
+                if self.grid.get(i, j).is_none() && let Some(value) = self.mandatory_value(i, j) {
+                    if self.grid.can_put(i, j, value) {
+                        self.put_a_mandatory_value(i, j, value);
+                    } else {
+                        let result = self.backtrack_to_latest_choice();
+                        return (result, !result);
                     }
+                    some_value_put = true;
                 }
             }
         }
@@ -206,8 +477,8 @@ impl Binero {
 
     /// Try to put a choice in the grid and returns whether or not it was possible
     fn try_a_choice(&mut self) -> bool {
-        for i in 0..self.grid.size().as_u8() {
-            for j in 0..self.grid.size().as_u8() {
+        for i in 0..self.grid.size().height() {
+            for j in 0..self.grid.size().width() {
                 if self.grid.get(i, j).is_none() {
                     let value = self.rand_value();
                     if self.grid.can_put(i, j, value) {
@@ -225,11 +496,13 @@ impl Binero {
         let latest_choice = self.history.latest_choice();
         match latest_choice {
             Some(choice) => {
-                let current = self.history.current_item().unwrap();
-                for i in choice..=current {
-                    let item = self.history.undo();
+                loop {
+                    let undone = self.history.current_item();
+                    let item = *self.history.undo();
                     self.grid.put(item.x_axis(), item.y_axis(), item.old_value());
-                    if i == current {
+                    self.notes.put(item.x_axis(), item.y_axis(), item.old_note());
+                    if undone == Some(choice) {
+                        self.history.forget(choice);
                         if let Some(bad_value) = item.new_value() {
                             let (x_axis, y_axis) = (item.x_axis(), item.y_axis());
                             let other_value = bad_value.the_other();
@@ -239,6 +512,7 @@ impl Binero {
                                 return self.backtrack_to_latest_choice();
                             }
                         }
+                        break;
                     }
                 }
                 true
@@ -247,73 +521,282 @@ impl Binero {
         }
     }
 
+    /// Returns whether or not a restored binero is consistent: the actions of its history are
+    /// all in the grid and leave the givens alone, and replaying them from the givens up to the
+    /// current one gives the current grid
+    fn is_consistent(&self) -> bool {
+        let size = self.size();
+        let in_grid = |item: &Item| item.x_axis() < size.height() && item.y_axis() < size.width() && !self.is_given(item.x_axis(), item.y_axis());
+        if !self.history.items().iter().all(in_grid) {
+            return false;
+        }
+        let mut path = Vec::new();
+        let mut current = self.history.current_item();
+        while let Some(index) = current {
+            path.push(index);
+            current = self.history.items()[index].parent();
+        }
+        let mut replay = self.givens.clone();
+        for &index in path.iter().rev() {
+            let item = &self.history.items()[index];
+            if replay.put(item.x_axis(), item.y_axis(), item.new_value()) != item.old_value() {
+                return false;
+            }
+        }
+        replay.to_compact_string() == self.grid.to_compact_string()
+    }
+
+    /// Returns whether or not a grid is a solution of givens: it is full, keeps every given and
+    /// breaks no rule
+    ///
+    /// # Arguments
+    ///
+    /// * `solution` - a grid
+    /// * `givens` - the values given at the beginning of a game
+    fn is_solution_of(solution: &Grid, givens: &Grid) -> bool {
+        if !solution.is_full() {
+            return false;
+        }
+        let size = solution.size();
+        let mut grid = solution.clone();
+        for i in 0..size.height() {
+            for j in 0..size.width() {
+                let value = grid.put(i, j, None).unwrap();
+                if givens.get(i, j).is_some_and(|given| given != value) || grid.breaks_row(i, j, value) || grid.breaks_column(i, j, value) {
+                    return false;
+                }
+                grid.put(i, j, Some(value));
+            }
+        }
+        true
+    }
+
+    /// Returns a binero whose givens are the values of a grid
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    fn from_grid(grid: &Grid) -> Binero {
+        Binero {
+            seed: None,
+            rng: ChaCha8Rng::seed_from_u64(0),
+            difficulty: Difficulty::Hard,
+            grid: grid.clone(),
+            givens: grid.clone(),
+            solution: grid.clone(),
+            notes: Notes::new(grid.size()),
+            history: History::new(),
+            rating: Rating::default(),
+        }
+    }
+
+    /// Returns the level of difficulty whose scores contain a score
+    ///
+    /// # Arguments
+    ///
+    /// * `score` - a score
+    fn difficulty_of(score: u16) -> Difficulty {
+        all::<Difficulty>().find(|&difficulty| Binero::score_band(difficulty).contains(&score)).unwrap_or(Difficulty::Hard)
+    }
+
     /// Returns a random `Value`
-    fn rand_value(&self) -> Value {
-        let value = rand::rng().random_range(0..2);
+    fn rand_value(&mut self) -> Value {
+        let value = self.rng.random_range(0..2);
         Value::from_u8(value).unwrap()
     }
 
-    /// Removes a certain amount of values from the grid according to the given difficulty
+    /// Removes values from the grid until the rating of the binero matches the given difficulty and
+    /// returns whether or not it does within `Binero::MAX_ATTEMPTS` attempts, the generation not
+    /// being cancelled
     ///
     /// # Arguments
     ///
     /// * `difficulty` - a level of difficulty
-    fn make_playable(&mut self, difficulty: Difficulty) {
-        let total = (self.grid.size().as_u8() as u16).pow(2);
-        let max_removed: u16 = match difficulty {
-            Difficulty::Beginner => total / 2,
-            Difficulty::Easy => total * 2 / 3,
-            Difficulty::Medium => total * 3 / 4,
-            Difficulty::Hard => total,
-        };
-        let indexes = self.shuffle_indexes();
-        let mut first = true;
-        for &(i, j) in indexes.iter() {
-            if self.grid.empty_values() >= max_removed {
-                break;
+    /// * `cancelled` - a flag set by another thread to stop the generation
+    fn make_playable(&mut self, difficulty: Difficulty, cancelled: &AtomicBool) -> bool {
+        let solution = self.solution.clone();
+        let band = Binero::score_band(difficulty);
+        for _ in 0..Binero::MAX_ATTEMPTS {
+            self.grid = solution.clone();
+            self.remove_values(difficulty, cancelled);
+            if cancelled.load(Ordering::Relaxed) {
+                return false;
             }
-            let value = self.grid.put(i, j, None);
-            if first {
-                first = false;
-                continue;
+            let rating = Solver::new(&self.grid).rate(&solution);
+            if band.contains(&rating.score()) {
+                self.rating = rating;
+                return true;
             }
-            self.check_a_value(i, j, value);
         }
+        false
     }
 
-    /// Checks whether or not a value can be removed from the grid keeping a unique solution for
-    /// the binero
+    /// Removes as many values as possible from the grid keeping a binero that can be solved using
+    /// the techniques allowed by the given difficulty or, when guessing is allowed, keeping a binero
+    /// whose unique solution can be proven quickly
     ///
     /// # Arguments
     ///
-    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
-    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
-    /// * `value` - an `Option<Value>`
-    fn check_a_value(&mut self, x_axis: u8, y_axis: u8, value: Option<Value>) {
-        let other_value = value.unwrap().the_other();
-        self.grid.put(x_axis, y_axis, Some(other_value));
-        let non_unique_solution = self.try_to_solve();
-        while self.try_to_undo().is_some() {
+    /// * `difficulty` - a level of difficulty
+    /// * `cancelled` - a flag set by another thread to stop removing values
+    fn remove_values(&mut self, difficulty: Difficulty, cancelled: &AtomicBool) {
+        let indexes = self.shuffle_indexes();
+        match Binero::hardest_technique(difficulty) {
+            Some(hardest) => {
+                for &(i, j) in indexes.iter() {
+                    if cancelled.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    let value = self.grid.put(i, j, None);
+                    if !Solver::new_limited(&self.grid, hardest).solve() {
+                        self.grid.put(i, j, value);
+                    }
+                }
+            },
+            None => {
+                for &(i, j) in indexes.iter() {
+                    if cancelled.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    let value = self.grid.put(i, j, None);
+                    if Solver::count_solutions_within(&self.grid, 2, Binero::MAX_EXPLORED_GRIDS) != Some(1) {
+                        self.grid.put(i, j, value);
+                    }
+                }
+            },
         }
-        self.grid.put(x_axis, y_axis, None);
-        self.history.clear();
-        if non_unique_solution {
-            self.grid.put(x_axis, y_axis, value);
+    }
+
+    /// Returns the hardest technique allowed by a difficulty or `None` if guessing is allowed
+    ///
+    /// # Arguments
+    ///
+    /// * `difficulty` - a level of difficulty
+    fn hardest_technique(difficulty: Difficulty) -> Option<Technique> {
+        match difficulty {
+            Difficulty::Beginner => Some(Technique::Sandwich),
+            Difficulty::Easy => Some(Technique::CountCompletion),
+            Difficulty::Medium => Some(Technique::Uniqueness),
+            Difficulty::Hard => None,
+        }
+    }
+
+    /// Returns the scores of the bineros matching a difficulty
+    ///
+    /// # Arguments
+    ///
+    /// * `difficulty` - a level of difficulty
+    fn score_band(difficulty: Difficulty) -> RangeInclusive<u16> {
+        match difficulty {
+            Difficulty::Beginner => 0..=Technique::Sandwich.weight(),
+            Difficulty::Easy => (Technique::Sandwich.weight() + 1)..=Technique::CountCompletion.weight(),
+            Difficulty::Medium => (Technique::CountCompletion.weight() + 1)..=Technique::Uniqueness.weight(),
+            Difficulty::Hard => (Technique::Uniqueness.weight() + 1)..=u16::MAX,
         }
     }
 
     /// Shuffles the x and y axis and returns them
-    fn shuffle_indexes(&self) -> Vec<(u8, u8)> {
-        let mut rng = rand::rng();
+    fn shuffle_indexes(&mut self) -> Vec<(u8, u8)> {
         let mut result: Vec<(u8, u8)> = Vec::new();
-        for i in 0..self.grid.size().as_u8() {
-            for j in 0..self.grid.size().as_u8() {
+        for i in 0..self.grid.size().height() {
+            for j in 0..self.grid.size().width() {
                 result.push((i, j));
             }
         }
-        result.shuffle(&mut rng);
+        result.shuffle(&mut self.rng);
         result
     }
+
+    const MAX_ATTEMPTS: u8 = 20;
+    const MAX_CHOICES: u32 = 100000;
+    const MAX_EXPLORED_GRIDS: u32 = 1000;
+}
+
+impl FromStr for Binero {
+    type Err = ParseError;
+
+    /// Returns the binero described by a text grid, with one line per row, `0` and `1` for the
+    /// values and `.` for the empty boxes, or by a single line as made by
+    /// `Binero::to_compact_string`
+    ///
+    /// Only the first block of lines is read, so that the givens can be read back from the text
+    /// made by `Binero::to_text`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - a text grid
+    fn from_str(text: &str) -> Result<Binero, ParseError> {
+        let block: Vec<&str> = text.lines().map(str::trim).skip_while(|line| line.is_empty()).take_while(|line| !line.is_empty()).collect();
+        let compact = match block.as_slice() {
+            [line] => line.split_once(Binero::COMPACT_SEPARATOR),
+            _ => None,
+        };
+        let rows: Vec<Vec<char>> = match compact {
+            Some((side, values)) => {
+                let size = Size::from_str(side).map_err(|_| ParseError::Size)?;
+                let values: Vec<char> = values.chars().collect();
+                if values.len() != size.width() as usize * size.height() as usize {
+                    return Err(ParseError::Size);
+                }
+                values.chunks(size.width() as usize).map(|row| row.to_vec()).collect()
+            },
+            None => block.iter()
+                .map(|line| line.chars().filter(|c| !c.is_whitespace()).collect::<Vec<char>>())
+                .collect(),
+        };
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return Err(ParseError::Size);
+        }
+        let size = u8::try_from(width).ok().zip(u8::try_from(rows.len()).ok())
+            .and_then(|(width, height)| Size::new(width, height))
+            .ok_or(ParseError::Size)?;
+        let mut grid = Grid::new(size);
+        for (i, row) in rows.iter().enumerate() {
+            for (j, &c) in row.iter().enumerate() {
+                if c != Grid::EMPTY_CHAR {
+                    let value = Value::from_char(c).ok_or(ParseError::Character(c))?;
+                    if !grid.can_put(i as u8, j as u8, value) {
+                        return Err(ParseError::Constraints);
+                    }
+                    grid.put(i as u8, j as u8, Some(value));
+                }
+            }
+        }
+        let mut result = Binero::from_grid(&grid);
+        result.solution = match Solver::solutions(&grid, 2).as_slice() {
+            [] => return Err(ParseError::NoSolution),
+            [solution] => solution.clone(),
+            _ => return Err(ParseError::SeveralSolutions),
+        };
+        result.rating = Solver::new(&result.givens).rate(&result.solution);
+        result.difficulty = Binero::difficulty_of(result.rating.score());
+        Ok(result)
+    }
+}
+
+/// The reasons why a text grid or a pack cannot be turned into bineros
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    Size,
+    Character(char),
+    Constraints,
+    NoSolution,
+    SeveralSolutions,
+    Document,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::Size => write!(f, "The width and the height of the grid must be supported!"),
+            ParseError::Character(c) => write!(f, "The character '{}' is not allowed in the grid!", c),
+            ParseError::Constraints => write!(f, "The values of the grid do not respect the rules!"),
+            ParseError::NoSolution => write!(f, "The grid has no solution!"),
+            ParseError::SeveralSolutions => write!(f, "The grid has several solutions!"),
+            ParseError::Document => write!(f, "The document is not a valid pack!"),
+        }
+    }
 }
 
 impl fmt::Display for Binero {
@@ -321,3 +804,44 @@ impl fmt::Display for Binero {
         write!(f, "Grid: {}\nHistory: {}", self.grid, self.history)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rating_matches_the_difficulty() {
+        for size in [Size::new(6, 6).unwrap(), Size::new(7, 9).unwrap(), Size::new(10, 10).unwrap()] {
+            for difficulty in all::<Difficulty>() {
+                for seed in 0..3 {
+                    let binero = Binero::new(size, difficulty, Some(seed));
+                    assert!(Binero::score_band(difficulty).contains(&binero.rating().score()), "{:?} {:?} {}", size, difficulty, seed);
+                    assert_eq!(Binero::difficulty_of(binero.rating().score()), difficulty);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn restore_checks_the_solution() {
+        let binero = Binero::new(Size::new(6, 6).unwrap(), Difficulty::Easy, Some(0));
+        let givens = binero.givens().to_compact_string();
+        let solution = binero.solution().to_compact_string();
+        let restore = |solution: &str| Binero::restore(binero.size(), Difficulty::Easy, None, &givens, &givens, solution, "-||");
+        assert!(restore(&solution).is_some());
+        let given = givens.find(|c| c != Grid::EMPTY_CHAR).unwrap();
+        let flipped = |index: usize| -> String {
+            solution.char_indices().map(|(i, c)| if i == index { if c == '0' { '1' } else { '0' } } else { c }).collect()
+        };
+        assert!(restore(&flipped(given)).is_none());
+        let empty = givens.find(Grid::EMPTY_CHAR).unwrap();
+        assert!(restore(&flipped(empty)).is_none());
+        assert!(restore(&format!("{}{}", Grid::EMPTY_CHAR, &solution[1..])).is_none());
+    }
+
+    #[test]
+    fn cancelled_generation() {
+        let cancelled = AtomicBool::new(true);
+        assert!(Binero::new_cancellable(Size::new(6, 6).unwrap(), Difficulty::Hard, Some(0), &cancelled).is_none());
+    }
+}
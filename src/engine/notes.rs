@@ -0,0 +1,96 @@
+//! # Notes
+//!
+//! `notes` manages the notes written by the player in the boxes of a grid
+
+use std::collections::HashMap;
+use crate::engine::grid::Grid;
+use crate::enums::{Note, Size};
+
+/// The notes of a binero grid are represented here, apart from its values
+#[derive(Clone)]
+pub struct Notes {
+    size: Size,
+    notes: HashMap<(u8, u8), Note>,
+}
+
+impl Notes {
+    /// Returns a grid without any note
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - a size
+    pub fn new(size: Size) -> Notes {
+        Notes {
+            size,
+            notes: HashMap::new(),
+        }
+    }
+
+    /// Returns the notes restored from a string made by `Notes::to_compact_string` or `None` if
+    /// that string is not valid
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - a size
+    /// * `compact` - a string slice representing the notes of the grid
+    pub fn from_compact_str(size: Size, compact: &str) -> Option<Notes> {
+        let chars: Vec<char> = compact.chars().collect();
+        if chars.len() != size.width() as usize * size.height() as usize {
+            return None;
+        }
+        let mut result = Notes::new(size);
+        for i in 0..size.height() {
+            for j in 0..size.width() {
+                let c = chars[i as usize * size.width() as usize + j as usize];
+                if c != Grid::EMPTY_CHAR {
+                    result.put(i, j, Some(Note::from_char(c)?));
+                }
+            }
+        }
+        Some(result)
+    }
+
+    /// Returns the notes as a string, row after row, with `Grid::EMPTY_CHAR` for the boxes
+    /// without a note
+    pub fn to_compact_string(&self) -> String {
+        let mut result = String::new();
+        for i in 0..self.size.height() {
+            for j in 0..self.size.width() {
+                match self.get(i, j) {
+                    Some(note) => result.push_str(&format!("{}", note)),
+                    None => result.push(Grid::EMPTY_CHAR),
+                }
+            }
+        }
+        result
+    }
+
+    /// Puts a note in a box and returns the previous one
+    ///
+    /// # Arguments
+    ///
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `note` - an `Option<Note>`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x_axis` or `y_axis` are greater than the size of the grid
+    pub fn put(&mut self, x_axis: u8, y_axis: u8, note: Option<Note>) -> Option<Note> {
+        assert!(x_axis < self.size.height() && y_axis < self.size.width());
+        match note {
+            Some(note) => self.notes.insert((x_axis, y_axis), note),
+            None => self.notes.remove(&(x_axis, y_axis)),
+        }
+    }
+
+    /// Returns the note of a box
+    ///
+    /// # Arguments
+    ///
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    pub fn get(&self, x_axis: u8, y_axis: u8) -> Option<Note> {
+        self.notes.get(&(x_axis, y_axis)).copied()
+    }
+}
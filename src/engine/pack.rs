@@ -0,0 +1,195 @@
+//! # Pack
+//!
+//! `pack` represents a collection of bineros generated in advance, stored as a JSON document
+
+use std::str::FromStr;
+use serde_json::{Value as Json, json};
+use crate::engine::{Binero, ParseError, grid::Grid, history::History, solver::Solver};
+use crate::enums::{Difficulty, Size, Value};
+
+/// A named collection of bineros
+pub struct Pack {
+    name: String,
+    puzzles: Vec<PackPuzzle>,
+}
+
+/// A binero of a pack, identified by its id
+pub struct PackPuzzle {
+    id: String,
+    difficulty: Difficulty,
+    givens: Grid,
+    solution: Grid,
+}
+
+impl Pack {
+    /// Returns a pack made of bineros, identified by their position in the pack
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the pack
+    /// * `bineros` - the bineros of the pack
+    pub fn new(name: &str, bineros: &[Binero]) -> Pack {
+        let puzzles = bineros.iter().enumerate().map(|(i, binero)| PackPuzzle {
+            id: format!("{}", i + 1),
+            difficulty: binero.difficulty(),
+            givens: binero.givens().clone(),
+            solution: binero.solution().clone(),
+        }).collect();
+        Pack {
+            name: name.to_owned(),
+            puzzles,
+        }
+    }
+
+    /// Returns the name of the pack
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the bineros of the pack
+    pub fn puzzles(&self) -> &[PackPuzzle] {
+        &self.puzzles
+    }
+
+    /// Returns the pack as a JSON document
+    pub fn to_json(&self) -> String {
+        let puzzles: Vec<Json> = self.puzzles.iter().map(|puzzle| json!({
+            "id": puzzle.id,
+            "size": format!("{}", puzzle.size()),
+            "difficulty": format!("{:?}", puzzle.difficulty),
+            "givens": puzzle.givens.rows(),
+            "solution": puzzle.solution.rows(),
+        })).collect();
+        let document = json!({
+            "name": self.name,
+            "puzzles": puzzles,
+        });
+        serde_json::to_string_pretty(&document).unwrap()
+    }
+}
+
+impl FromStr for Pack {
+    type Err = ParseError;
+
+    /// Returns the pack described by a JSON document, as made by `Pack::to_json`
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - a JSON document
+    fn from_str(text: &str) -> Result<Pack, ParseError> {
+        let document: Json = serde_json::from_str(text).map_err(|_| ParseError::Document)?;
+        let name = document["name"].as_str().ok_or(ParseError::Document)?;
+        let puzzles = document["puzzles"].as_array().ok_or(ParseError::Document)?
+            .iter()
+            .map(PackPuzzle::from_json)
+            .collect::<Result<Vec<PackPuzzle>, ParseError>>()?;
+        Ok(Pack {
+            name: name.to_owned(),
+            puzzles,
+        })
+    }
+}
+
+impl PackPuzzle {
+    /// Returns the id of the binero in its pack
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the size of the binero
+    pub fn size(&self) -> Size {
+        self.givens.size()
+    }
+
+    /// Returns the difficulty of the binero
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Returns a new game with the binero
+    pub fn binero(&self) -> Binero {
+        let givens = self.givens.to_compact_string();
+        Binero::restore(self.size(), self.difficulty, None, &givens, &givens, &self.solution.to_compact_string(), &History::new().to_compact_string()).unwrap()
+    }
+
+    /// Returns the binero described by a JSON value of a pack
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle` - a JSON value
+    fn from_json(puzzle: &Json) -> Result<PackPuzzle, ParseError> {
+        let id = match &puzzle["id"] {
+            Json::String(id) => id.clone(),
+            Json::Number(id) => format!("{}", id),
+            _ => return Err(ParseError::Document),
+        };
+        let size = match &puzzle["size"] {
+            Json::String(size) => Size::from_str(size).ok(),
+            Json::Number(side) => Size::from_str(&format!("{}", side)).ok(),
+            _ => None,
+        }.ok_or(ParseError::Size)?;
+        let difficulty = puzzle["difficulty"].as_str().and_then(|difficulty| Difficulty::from_str(difficulty).ok()).ok_or(ParseError::Document)?;
+        let givens = PackPuzzle::grid_from_json(size, &puzzle["givens"])?;
+        let solution = PackPuzzle::grid_from_json(size, &puzzle["solution"])?;
+        if !solution.is_full() || !PackPuzzle::respects_rules(&solution) {
+            return Err(ParseError::Constraints);
+        }
+        for i in 0..size.height() {
+            for j in 0..size.width() {
+                if givens.get(i, j).is_some_and(|value| Some(value) != solution.get(i, j)) {
+                    return Err(ParseError::Constraints);
+                }
+            }
+        }
+        match Solver::solutions(&givens, 2).len() {
+            0 => return Err(ParseError::NoSolution),
+            1 => (),
+            _ => return Err(ParseError::SeveralSolutions),
+        }
+        Ok(PackPuzzle {
+            id,
+            difficulty,
+            givens,
+            solution,
+        })
+    }
+
+    /// Returns the grid described by the array of its rows in a JSON value
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the grid
+    /// * `rows` - a JSON value
+    fn grid_from_json(size: Size, rows: &Json) -> Result<Grid, ParseError> {
+        let rows = rows.as_array().ok_or(ParseError::Document)?;
+        let mut compact = String::new();
+        for row in rows {
+            compact.push_str(row.as_str().ok_or(ParseError::Document)?);
+        }
+        if let Some(c) = compact.chars().find(|&c| c != Grid::EMPTY_CHAR && Value::from_char(c).is_none()) {
+            return Err(ParseError::Character(c));
+        }
+        Grid::from_compact_str(size, &compact).ok_or(ParseError::Size)
+    }
+
+    /// Returns whether or not the values of a grid respect the rules
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    fn respects_rules(grid: &Grid) -> bool {
+        let size = grid.size();
+        let mut result = Grid::new(size);
+        for i in 0..size.height() {
+            for j in 0..size.width() {
+                if let Some(value) = grid.get(i, j) {
+                    if !result.can_put(i, j, value) {
+                        return false;
+                    }
+                    result.put(i, j, Some(value));
+                }
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,875 @@
+//! # Solver
+//!
+//! `solver` contains the functions that solve a binero step by step, the way a human would do
+
+use std::fmt;
+use enum_iterator::{all, Sequence};
+use crate::engine::grid::Grid;
+use crate::enums::{Size, Value};
+
+/// The techniques used to deduce a value, from the easiest to the hardest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Sequence)]
+pub enum Technique {
+    Pair,
+    Sandwich,
+    CountCompletion,
+    Uniqueness,
+    LookAhead,
+}
+
+impl Technique {
+    /// Returns the weight of the technique in the score of a binero
+    pub fn weight(&self) -> u16 {
+        match *self {
+            Technique::Pair => 1,
+            Technique::Sandwich => 2,
+            Technique::CountCompletion => 4,
+            Technique::Uniqueness => 8,
+            Technique::LookAhead => 16,
+        }
+    }
+}
+
+impl fmt::Display for Technique {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let printable = match *self {
+            Technique::Pair => "Pair",
+            Technique::Sandwich => "Sandwich",
+            Technique::CountCompletion => "Count completion",
+            Technique::Uniqueness => "Uniqueness",
+            Technique::LookAhead => "Look-ahead",
+        };
+        write!(f, "{}", printable)
+    }
+}
+
+/// A row or a column of the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Line {
+    Row(u8),
+    Column(u8),
+}
+
+impl Line {
+    /// Returns the x-axis and the y-axis of the nth box of the line
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - the index of the box in the line
+    fn cell(&self, k: u8) -> (u8, u8) {
+        match *self {
+            Line::Row(i) => (i, k),
+            Line::Column(j) => (k, j),
+        }
+    }
+
+    /// Returns the number of boxes of the line in a grid
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the grid
+    fn length(&self, size: Size) -> u8 {
+        match *self {
+            Line::Row(_) => size.width(),
+            Line::Column(_) => size.height(),
+        }
+    }
+
+    /// Returns whether or not two lines are both rows or both columns
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - another line
+    fn is_parallel_to(&self, other: &Line) -> bool {
+        matches!((self, other), (Line::Row(_), Line::Row(_)) | (Line::Column(_), Line::Column(_)))
+    }
+}
+
+/// A step of the resolution of a binero, that is to say a value deduced with a technique
+#[derive(Clone, Copy)]
+pub struct Step {
+    x_axis: u8,
+    y_axis: u8,
+    value: Value,
+    technique: Technique,
+    line: Option<Line>,
+}
+
+impl Step {
+    /// Returns the x-axis of the deduced value
+    pub fn x_axis(&self) -> u8 {
+        self.x_axis
+    }
+
+    /// Returns the y-axis of the deduced value
+    pub fn y_axis(&self) -> u8 {
+        self.y_axis
+    }
+
+    /// Returns the deduced value
+    pub fn value(&self) -> Value {
+        self.value
+    }
+
+    /// Returns the technique used to deduce the value
+    pub fn technique(&self) -> Technique {
+        self.technique
+    }
+
+    /// Returns the row or the column that made the deduction possible, if there is one
+    pub fn line(&self) -> Option<Line> {
+        self.line
+    }
+}
+
+/// The rating of a binero: the hardest technique needed to solve it and the number of values that
+/// had to be guessed because no technique was enough
+#[derive(Clone, Copy, Default)]
+pub struct Rating {
+    hardest: Option<Technique>,
+    guesses: u16,
+}
+
+impl Rating {
+    /// Returns the hardest technique needed to solve the binero
+    pub fn hardest(&self) -> Option<Technique> {
+        self.hardest
+    }
+
+    /// Returns the number of values that had to be guessed
+    pub fn guesses(&self) -> u16 {
+        self.guesses
+    }
+
+    /// Returns the score of the binero: the higher, the harder
+    pub fn score(&self) -> u16 {
+        let hardest = match self.hardest {
+            Some(technique) => technique.weight(),
+            None => 0,
+        };
+        hardest + self.guesses * Rating::GUESS_WEIGHT
+    }
+
+    const GUESS_WEIGHT: u16 = 32;
+}
+
+/// A solver applying techniques one step at a time on a copy of a grid
+pub struct Solver {
+    grid: Grid,
+    steps: Vec<Step>,
+    hardest: Technique,
+}
+
+impl Solver {
+    /// Returns a solver working on a copy of the given grid
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    pub fn new(grid: &Grid) -> Solver {
+        Solver::new_limited(grid, Technique::LookAhead)
+    }
+
+    /// Returns a solver working on a copy of the given grid and using only the techniques not
+    /// harder than the given one
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    /// * `hardest` - the hardest technique that can be used
+    pub fn new_limited(grid: &Grid, hardest: Technique) -> Solver {
+        Solver {
+            grid: grid.clone(),
+            steps: Vec::new(),
+            hardest,
+        }
+    }
+
+    /// Returns the grid as the solver has filled it
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Returns the steps applied so far
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// Returns the next step that can be deduced using the easiest possible technique, or `None`
+    /// if the solver is stuck
+    pub fn next_step(&self) -> Option<Step> {
+        Solver::find_step(&self.grid, self.hardest)
+    }
+
+    /// Applies the next step and returns it, or returns `None` if the solver is stuck
+    pub fn apply_next_step(&mut self) -> Option<Step> {
+        let step = self.next_step()?;
+        self.grid.put(step.x_axis, step.y_axis, Some(step.value));
+        self.steps.push(step);
+        Some(step)
+    }
+
+    /// Applies steps until the grid is full or the solver is stuck and returns whether or not the
+    /// grid is full
+    pub fn solve(&mut self) -> bool {
+        while !self.grid.is_full() && self.apply_next_step().is_some() {
+        }
+        self.grid.is_full()
+    }
+
+    /// Solves the grid, guessing a value from the solution each time the solver is stuck, and
+    /// returns the rating of the binero
+    ///
+    /// # Arguments
+    ///
+    /// * `solution` - the solution of the binero
+    pub fn rate(&mut self, solution: &Grid) -> Rating {
+        let mut guesses = 0;
+        while !self.solve() {
+            if let Some((x_axis, y_axis)) = self.first_empty_box() {
+                self.grid.put(x_axis, y_axis, solution.get(x_axis, y_axis));
+                guesses += 1;
+            }
+        }
+        Rating {
+            hardest: self.steps.iter().map(|step| step.technique).max(),
+            guesses,
+        }
+    }
+
+    /// Returns the solutions of a grid, stopping as soon as `limit` solutions are found
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    /// * `limit` - the maximum number of solutions to find
+    pub fn solutions(grid: &Grid, limit: usize) -> Vec<Grid> {
+        let mut result = Vec::new();
+        let mut budget = u32::MAX;
+        Solver::search(grid, limit, &mut result, &mut budget);
+        result
+    }
+
+    /// Returns the number of solutions of a grid, counting no further than `limit`: to prove that a
+    /// binero has exactly one solution, a limit of 2 is enough
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    /// * `limit` - the maximum number of solutions to count
+    pub fn count_solutions(grid: &Grid, limit: usize) -> usize {
+        Solver::solutions(grid, limit).len()
+    }
+
+    /// Returns the number of solutions of a grid, counting no further than `limit`, or `None` if
+    /// the number of grids to explore exceeds a budget before the count is known
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    /// * `limit` - the maximum number of solutions to count
+    /// * `budget` - the maximum number of grids to explore
+    pub fn count_solutions_within(grid: &Grid, limit: usize, budget: u32) -> Option<usize> {
+        let mut solutions = Vec::new();
+        let mut budget = budget;
+        if Solver::search(grid, limit, &mut solutions, &mut budget) {
+            Some(solutions.len())
+        } else {
+            None
+        }
+    }
+
+    /// Explores every possible value of the empty boxes of a grid, after the values that are forced
+    /// are put, adds the full grids found to the solutions and returns whether or not the
+    /// exploration ended within the budget
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    /// * `limit` - the maximum number of solutions to find
+    /// * `solutions` - the solutions found so far
+    /// * `budget` - the number of grids that can still be explored
+    fn search(grid: &Grid, limit: usize, solutions: &mut Vec<Grid>, budget: &mut u32) -> bool {
+        if solutions.len() >= limit {
+            return true;
+        }
+        if *budget == 0 {
+            return false;
+        }
+        *budget -= 1;
+        let mut grid = grid.clone();
+        if !Solver::put_forced_values(&mut grid) {
+            return true;
+        }
+        match Solver::most_constrained_box(&grid) {
+            Some((x_axis, y_axis)) => {
+                for value in [Value::First, Value::Second] {
+                    if grid.can_put(x_axis, y_axis, value) {
+                        let mut next = grid.clone();
+                        next.put(x_axis, y_axis, Some(value));
+                        if !Solver::search(&next, limit, solutions, budget) {
+                            return false;
+                        }
+                        if solutions.len() >= limit {
+                            return true;
+                        }
+                    }
+                }
+            },
+            None => solutions.push(grid),
+        }
+        true
+    }
+
+    /// Puts every value that is the only one accepted by its box, until there is none, and returns
+    /// whether or not the grid is still consistent, i.e. has no empty box that cannot accept any value
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    fn put_forced_values(grid: &mut Grid) -> bool {
+        let size = grid.size();
+        let mut some_value_put = true;
+        while some_value_put {
+            some_value_put = false;
+            for x_axis in 0..size.height() {
+                for y_axis in 0..size.width() {
+                    if grid.get(x_axis, y_axis).is_some() {
+                        continue;
+                    }
+                    let first = grid.can_put(x_axis, y_axis, Value::First);
+                    let second = grid.can_put(x_axis, y_axis, Value::Second);
+                    match (first, second) {
+                        (false, false) => return false,
+                        (true, false) => grid.put(x_axis, y_axis, Some(Value::First)),
+                        (false, true) => grid.put(x_axis, y_axis, Some(Value::Second)),
+                        (true, true) => continue,
+                    };
+                    some_value_put = true;
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns the x-axis and the y-axis of the empty box whose row and column have the fewest empty
+    /// boxes, if any
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    fn most_constrained_box(grid: &Grid) -> Option<(u8, u8)> {
+        let size = grid.size();
+        let empty_in = |line: Line| (0..line.length(size)).filter(|&k| {
+            let (x_axis, y_axis) = line.cell(k);
+            grid.get(x_axis, y_axis).is_none()
+        }).count();
+        let rows: Vec<usize> = (0..size.height()).map(|x_axis| empty_in(Line::Row(x_axis))).collect();
+        let columns: Vec<usize> = (0..size.width()).map(|y_axis| empty_in(Line::Column(y_axis))).collect();
+        (0..size.height()).flat_map(|x_axis| (0..size.width()).map(move |y_axis| (x_axis, y_axis)))
+            .filter(|&(x_axis, y_axis)| grid.get(x_axis, y_axis).is_none())
+            .min_by_key(|&(x_axis, y_axis)| rows[x_axis as usize].min(columns[y_axis as usize]))
+    }
+
+    /// Returns the x-axis and the y-axis of the first empty box of the grid, if any
+    fn first_empty_box(&self) -> Option<(u8, u8)> {
+        let size = self.grid.size();
+        (0..size.height()).flat_map(|x_axis| (0..size.width()).map(move |y_axis| (x_axis, y_axis)))
+            .find(|&(x_axis, y_axis)| self.grid.get(x_axis, y_axis).is_none())
+    }
+
+    /// Returns the step deduced using the easiest technique not harder than the given one, or `None`
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    /// * `hardest` - the hardest technique that can be used
+    fn find_step(grid: &Grid, hardest: Technique) -> Option<Step> {
+        for technique in all::<Technique>() {
+            if technique > hardest {
+                break;
+            }
+            let step = match technique {
+                Technique::Pair => Solver::find_pair(grid),
+                Technique::Sandwich => Solver::find_sandwich(grid),
+                Technique::CountCompletion => Solver::find_count_completion(grid),
+                Technique::Uniqueness => Solver::find_uniqueness(grid),
+                Technique::LookAhead => Solver::find_look_ahead(grid),
+            };
+            if step.is_some() {
+                return step;
+            }
+        }
+        None
+    }
+
+    /// Returns a value that must be put next to two identical values, or `None`
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    fn find_pair(grid: &Grid) -> Option<Step> {
+        Solver::find_in_lines(grid, Technique::Pair, Solver::forbidden_by_pair)
+    }
+
+    /// Returns a value that must be put between two identical values, or `None`
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    fn find_sandwich(grid: &Grid) -> Option<Step> {
+        Solver::find_in_lines(grid, Technique::Sandwich, Solver::forbidden_by_sandwich)
+    }
+
+    /// Returns a value that must be put in a line already containing as much of the other value as
+    /// possible, or `None`
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    fn find_count_completion(grid: &Grid) -> Option<Step> {
+        Solver::find_in_lines(grid, Technique::CountCompletion, Solver::forbidden_by_count_completion)
+    }
+
+    /// Returns the value that cannot be put in the nth box of a line because it is next to two
+    /// identical values, or `None`
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - the values of a line
+    /// * `k` - the index of the box in the line
+    fn forbidden_by_pair(values: &[Option<Value>], k: usize) -> Option<Value> {
+        let length = values.len();
+        if k >= 2 && values[k - 1].is_some() && values[k - 1] == values[k - 2] {
+            values[k - 1]
+        } else if k + 2 < length && values[k + 1].is_some() && values[k + 1] == values[k + 2] {
+            values[k + 1]
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value that cannot be put in the nth box of a line because it is between two
+    /// identical values, or `None`
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - the values of a line
+    /// * `k` - the index of the box in the line
+    fn forbidden_by_sandwich(values: &[Option<Value>], k: usize) -> Option<Value> {
+        if k >= 1 && k + 1 < values.len() && values[k - 1].is_some() && values[k - 1] == values[k + 1] {
+            values[k - 1]
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value that cannot be put in a line because it already contains as much of it as
+    /// possible, or `None`
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - the values of a line
+    /// * `_k` - the index of the box in the line, which does not matter
+    fn forbidden_by_count_completion(values: &[Option<Value>], _k: usize) -> Option<Value> {
+        let max = Grid::max_per_line(values.len() as u8);
+        [Value::First, Value::Second].into_iter()
+            .find(|&value| values.iter().filter(|&&v| v == Some(value)).count() as u8 >= max)
+    }
+
+    /// Calls a function on every empty box of every line and returns the first step found: the
+    /// function returns the value that *cannot* be put in the box, if any
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    /// * `technique` - the technique implemented by the function
+    /// * `forbidden_value` - a function returning the value forbidden in the nth box of a line
+    fn find_in_lines<F>(grid: &Grid, technique: Technique, forbidden_value: F) -> Option<Step>
+        where F: Fn(&[Option<Value>], usize) -> Option<Value> {
+        for line in Solver::lines(grid) {
+            let values = Solver::values(grid, &line);
+            for k in 0..values.len() {
+                if values[k].is_some() {
+                    continue;
+                }
+                if let Some(forbidden) = forbidden_value(&values, k) {
+                    let (x_axis, y_axis) = line.cell(k as u8);
+                    let value = forbidden.the_other();
+                    if grid.can_put(x_axis, y_axis, value) {
+                        return Some(Step {
+                            x_axis,
+                            y_axis,
+                            value,
+                            technique,
+                            line: Some(line),
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns a value that must be put in a nearly full line so that it is not identical to
+    /// another line, or `None`
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    fn find_uniqueness(grid: &Grid) -> Option<Step> {
+        let lines = Solver::lines(grid);
+        for line in &lines {
+            let values = Solver::values(grid, line);
+            let empty: Vec<usize> = (0..values.len()).filter(|&k| values[k].is_none()).collect();
+            if empty.is_empty() || empty.len() > Solver::MAX_EMPTY_FOR_UNIQUENESS {
+                continue;
+            }
+            let full_lines: Vec<Vec<Option<Value>>> = lines.iter()
+                .filter(|other| *other != line && other.is_parallel_to(line))
+                .map(|other| Solver::values(grid, other))
+                .filter(|other| !other.contains(&None))
+                .collect();
+            if full_lines.is_empty() {
+                continue;
+            }
+            let mut valid = Vec::new();
+            for combination in 0..(1u32 << empty.len()) {
+                let mut completion = values.clone();
+                for (bit, &k) in empty.iter().enumerate() {
+                    completion[k] = Value::from_u8(((combination >> bit) & 1) as u8);
+                }
+                if Solver::is_valid_line(&completion, Grid::max_per_line(completion.len() as u8)) {
+                    valid.push(completion);
+                }
+            }
+            let unique: Vec<&Vec<Option<Value>>> = valid.iter().filter(|completion| !full_lines.contains(completion)).collect();
+            for &k in &empty {
+                let same_value = |completions: &[&Vec<Option<Value>>]| {
+                    completions.first().map(|first| first[k]).filter(|&value| completions.iter().all(|completion| completion[k] == value))
+                };
+                let all_valid: Vec<&Vec<Option<Value>>> = valid.iter().collect();
+                if same_value(&all_valid).is_some() {
+                    continue;
+                }
+                if let Some(Some(value)) = same_value(&unique) {
+                    let (x_axis, y_axis) = line.cell(k as u8);
+                    if grid.can_put(x_axis, y_axis, value) {
+                        return Some(Step {
+                            x_axis,
+                            y_axis,
+                            value,
+                            technique: Technique::Uniqueness,
+                            line: Some(*line),
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns a value whose other value leads to a contradiction when the easier techniques are
+    /// applied, or `None`: a value whose other value breaks a rule at once is labelled with the
+    /// technique spotting that rule instead
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    fn find_look_ahead(grid: &Grid) -> Option<Step> {
+        for x_axis in 0..grid.size().height() {
+            for y_axis in 0..grid.size().width() {
+                if grid.get(x_axis, y_axis).is_some() {
+                    continue;
+                }
+                for value in [Value::First, Value::Second] {
+                    let other_value = value.the_other();
+                    if !grid.can_put(x_axis, y_axis, other_value) {
+                        continue;
+                    }
+                    if !grid.can_put(x_axis, y_axis, value) {
+                        let (technique, line) = Solver::forbidding_technique(grid, x_axis, y_axis, value);
+                        return Some(Step {
+                            x_axis,
+                            y_axis,
+                            value: other_value,
+                            technique,
+                            line: Some(line),
+                        });
+                    }
+                    if Solver::leads_to_contradiction(grid, x_axis, y_axis, value) {
+                        return Some(Step {
+                            x_axis,
+                            y_axis,
+                            value: other_value,
+                            technique: Technique::LookAhead,
+                            line: None,
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the easiest technique spotting that a value cannot be put in a box, with the row or
+    /// the column where it does
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `value` - a `Value` breaking a rule in that box
+    fn forbidding_technique(grid: &Grid, x_axis: u8, y_axis: u8, value: Value) -> (Technique, Line) {
+        let lines = [(Line::Row(x_axis), y_axis as usize), (Line::Column(y_axis), x_axis as usize)];
+        for technique in [Technique::Pair, Technique::Sandwich, Technique::CountCompletion] {
+            for (line, k) in lines {
+                let values = Solver::values(grid, &line);
+                let forbidden = match technique {
+                    Technique::Pair => Solver::forbidden_by_pair(&values, k),
+                    Technique::Sandwich => Solver::forbidden_by_sandwich(&values, k),
+                    _ => Solver::forbidden_by_count_completion(&values, k),
+                };
+                if forbidden == Some(value) {
+                    return (technique, line);
+                }
+            }
+        }
+        if grid.breaks_row(x_axis, y_axis, value) {
+            (Technique::Uniqueness, Line::Row(x_axis))
+        } else {
+            (Technique::Uniqueness, Line::Column(y_axis))
+        }
+    }
+
+    /// Returns whether or not putting a value in a copy of the grid and applying the techniques
+    /// easier than the look-ahead leads to a contradiction
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `value` - a `Value`
+    fn leads_to_contradiction(grid: &Grid, x_axis: u8, y_axis: u8, value: Value) -> bool {
+        let mut grid = grid.clone();
+        grid.put(x_axis, y_axis, Some(value));
+        while let Some(step) = Solver::find_step(&grid, Technique::Uniqueness) {
+            grid.put(step.x_axis, step.y_axis, Some(step.value));
+        }
+        Solver::has_dead_end(&grid)
+    }
+
+    /// Returns whether or not the grid contains an empty box that cannot accept any value
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    fn has_dead_end(grid: &Grid) -> bool {
+        for x_axis in 0..grid.size().height() {
+            for y_axis in 0..grid.size().width() {
+                if grid.get(x_axis, y_axis).is_none() &&
+                    !grid.can_put(x_axis, y_axis, Value::First) &&
+                    !grid.can_put(x_axis, y_axis, Value::Second) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Returns whether or not a full line respects the constraints about the number of each value
+    /// and about the values side by side
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - the values of a line
+    /// * `max` - the maximum number of a value in the line
+    fn is_valid_line(values: &[Option<Value>], max: u8) -> bool {
+        for value in [Value::First, Value::Second] {
+            if values.iter().filter(|&&v| v == Some(value)).count() as u8 > max {
+                return false;
+            }
+        }
+        !values.windows(3).any(|window| window[0] == window[1] && window[1] == window[2])
+    }
+
+    /// Returns all the rows and then all the columns of a grid
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    fn lines(grid: &Grid) -> Vec<Line> {
+        let size = grid.size();
+        (0..size.height()).map(Line::Row).chain((0..size.width()).map(Line::Column)).collect()
+    }
+
+    /// Returns the values of a line of a grid
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a grid
+    /// * `line` - a line
+    fn values(grid: &Grid, line: &Line) -> Vec<Option<Value>> {
+        (0..line.length(grid.size())).map(|k| {
+            let (x_axis, y_axis) = line.cell(k);
+            grid.get(x_axis, y_axis)
+        }).collect()
+    }
+
+    const MAX_EMPTY_FOR_UNIQUENESS: usize = 4;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::Binero;
+    use crate::enums::Difficulty;
+
+    /// Returns whether or not every value of a full grid can be put back in its box
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - a full grid
+    fn is_valid(grid: &Grid) -> bool {
+        let size = grid.size();
+        grid.is_full() && (0..size.height()).all(|i| (0..size.width()).all(|j| {
+            let mut other = grid.clone();
+            let value = other.put(i, j, None).unwrap();
+            other.can_put(i, j, value)
+        }))
+    }
+
+    /// Returns a grid made from its values, row after row
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - the number of columns
+    /// * `height` - the number of rows
+    /// * `compact` - the values of the grid, with `.` for the empty boxes
+    fn grid_from(width: u8, height: u8, compact: &str) -> Grid {
+        Grid::from_compact_str(Size::new(width, height).unwrap(), compact).unwrap()
+    }
+
+    #[test]
+    fn pair() {
+        let grid = grid_from(6, 6, concat!("00....", "......", "......", "......", "......", "......"));
+        let step = Solver::find_pair(&grid).unwrap();
+        assert_eq!((step.x_axis(), step.y_axis()), (0, 2));
+        assert!(step.value() == Value::Second);
+        assert_eq!(step.technique(), Technique::Pair);
+        assert_eq!(step.line(), Some(Line::Row(0)));
+        assert_eq!(Solver::new(&grid).next_step().map(|step| step.technique()), Some(Technique::Pair));
+    }
+
+    #[test]
+    fn sandwich() {
+        let grid = grid_from(6, 6, concat!("1.....", "......", "1.....", "......", "......", "......"));
+        assert!(Solver::find_pair(&grid).is_none());
+        let step = Solver::find_sandwich(&grid).unwrap();
+        assert_eq!((step.x_axis(), step.y_axis()), (1, 0));
+        assert!(step.value() == Value::First);
+        assert_eq!(step.technique(), Technique::Sandwich);
+        assert_eq!(step.line(), Some(Line::Column(0)));
+        assert_eq!(Solver::new(&grid).next_step().map(|step| step.technique()), Some(Technique::Sandwich));
+    }
+
+    #[test]
+    fn count_completion() {
+        let grid = grid_from(6, 6, concat!("0010..", "......", "......", "......", "......", "......"));
+        assert!(Solver::find_pair(&grid).is_none());
+        assert!(Solver::find_sandwich(&grid).is_none());
+        let step = Solver::find_count_completion(&grid).unwrap();
+        assert_eq!((step.x_axis(), step.y_axis()), (0, 4));
+        assert!(step.value() == Value::Second);
+        assert_eq!(step.technique(), Technique::CountCompletion);
+        assert_eq!(step.line(), Some(Line::Row(0)));
+        assert_eq!(Solver::new(&grid).next_step().map(|step| step.technique()), Some(Technique::CountCompletion));
+    }
+
+    #[test]
+    fn uniqueness() {
+        let grid = grid_from(6, 6, concat!("011010", "01.01.", "......", "......", "......", "......"));
+        let step = Solver::find_uniqueness(&grid).unwrap();
+        assert_eq!((step.x_axis(), step.y_axis()), (1, 2));
+        assert!(step.value() == Value::First);
+        assert_eq!(step.technique(), Technique::Uniqueness);
+        assert_eq!(step.line(), Some(Line::Row(1)));
+    }
+
+    #[test]
+    fn look_ahead() {
+        let mut solver = Solver::new(&grid_from(6, 6, concat!("...1.0", "1..1..", "....1.", "......", "1.0...", "..0...")));
+        loop {
+            let grid = solver.grid().clone();
+            let step = solver.apply_next_step().unwrap();
+            if step.technique() == Technique::LookAhead {
+                assert_eq!(step.line(), None);
+                assert!(Solver::find_step(&grid, Technique::Uniqueness).is_none());
+                assert!(Solver::leads_to_contradiction(&grid, step.x_axis(), step.y_axis(), step.value().the_other()));
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn forced_value_is_not_a_look_ahead() {
+        let grid = grid_from(6, 6, concat!("00....", "......", "......", "......", "......", "......"));
+        let step = Solver::find_look_ahead(&grid).unwrap();
+        assert_eq!((step.x_axis(), step.y_axis()), (0, 2));
+        assert!(step.value() == Value::Second);
+        assert_eq!(step.technique(), Technique::Pair);
+        assert_eq!(step.line(), Some(Line::Row(0)));
+        let grid = grid_from(7, 6, concat!("0110100", "011010.", ".......", ".......", ".......", "......."));
+        let step = Solver::find_look_ahead(&grid).unwrap();
+        assert_eq!((step.x_axis(), step.y_axis()), (1, 6));
+        assert!(step.value() == Value::Second);
+        assert_eq!(step.technique(), Technique::Uniqueness);
+        assert_eq!(step.line(), Some(Line::Row(1)));
+    }
+
+    #[test]
+    fn no_solution() {
+        let mut grid = Grid::new(Size::new(6, 6).unwrap());
+        grid.put(0, 0, Some(Value::First));
+        grid.put(0, 1, Some(Value::First));
+        grid.put(0, 3, Some(Value::Second));
+        grid.put(0, 4, Some(Value::Second));
+        assert!(Solver::solutions(&grid, 2).is_empty());
+        assert_eq!(Solver::count_solutions_within(&grid, 2, Binero::MAX_EXPLORED_GRIDS), Some(0));
+    }
+
+    #[test]
+    fn one_solution() {
+        let binero = Binero::new(Size::new(8, 8).unwrap(), Difficulty::Hard, Some(1));
+        let solutions = Solver::solutions(binero.givens(), 2);
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].to_compact_string(), binero.solution().to_compact_string());
+        assert_eq!(Solver::count_solutions_within(binero.givens(), 2, Binero::MAX_EXPLORED_GRIDS), Some(1));
+    }
+
+    #[test]
+    fn several_solutions() {
+        let grid = Grid::new(Size::new(6, 6).unwrap());
+        let solutions = Solver::solutions(&grid, 3);
+        assert_eq!(solutions.len(), 3);
+        assert!(solutions.iter().all(is_valid));
+        assert_ne!(solutions[0].to_compact_string(), solutions[1].to_compact_string());
+        assert_eq!(Solver::count_solutions_within(&grid, 2, Binero::MAX_EXPLORED_GRIDS), Some(2));
+        assert_eq!(Solver::count_solutions_within(&grid, 2, 1), None);
+    }
+
+    #[test]
+    fn odd_size() {
+        let size = Size::new(7, 9).unwrap();
+        let solutions = Solver::solutions(&Grid::new(size), 2);
+        assert_eq!(solutions.len(), 2);
+        assert!(solutions.iter().all(is_valid));
+        let binero = Binero::new(size, Difficulty::Medium, Some(1));
+        let solutions = Solver::solutions(binero.givens(), 2);
+        assert_eq!(solutions.len(), 1);
+        assert!(is_valid(&solutions[0]));
+        assert_eq!(solutions[0].to_compact_string(), binero.solution().to_compact_string());
+    }
+}
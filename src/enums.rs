@@ -2,12 +2,11 @@
 //!
 //! `enums` contains several enums used in all the game
 
-use std::fmt;
-use tr::tr;
+use std::{fmt, str::FromStr};
 use enum_iterator::{all, Sequence};
 
 /// The four possible levels of difficulty
-#[derive(Debug, Clone, Copy, Sequence)]
+#[derive(Debug, Clone, Copy, Sequence, PartialEq, Eq, Hash)]
 pub enum Difficulty {
     Beginner,
     Easy,
@@ -15,73 +14,127 @@ pub enum Difficulty {
     Hard,
 }
 
-impl Difficulty {
+impl FromStr for Difficulty {
+    type Err = ParseEnumError;
+
     /// Returns the `Difficulty` corresponding to a given string slice
     ///
     /// # Arguments
     ///
     /// * `difficulty` - a string slice representing a difficulty
-    pub fn from_str(difficulty: &str) -> Option<Difficulty> {
-        for a_difficulty in all::<Difficulty>() {
-            if format!("{:?}", a_difficulty) == difficulty {
-                return Some(a_difficulty);
-            }
-        }
-        None
+    fn from_str(difficulty: &str) -> Result<Difficulty, ParseEnumError> {
+        all::<Difficulty>().find(|a_difficulty| format!("{:?}", a_difficulty) == difficulty).ok_or(ParseEnumError)
     }
 }
 
 impl fmt::Display for Difficulty {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let printable = match *self {
-            Difficulty::Beginner => tr!("Beginner"),
-            Difficulty::Easy => tr!("Easy"),
-            Difficulty::Medium => tr!("Medium"),
-            Difficulty::Hard => tr!("Hard"),
+            Difficulty::Beginner => "Beginner",
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
         };
         write!(f, "{}", printable)
     }
 }
 
-/// The possible sizes of a binero
-#[derive(Copy, Clone, Debug, Sequence, PartialEq, Eq, Hash)]
-pub enum Size {
-    Side6,
-    Side8,
-    Side10,
-    Side12,
-    Side14,
-    Side16,
+/// The size of a binero, given by its width and its height
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Size {
+    width: u8,
+    height: u8,
 }
 
 impl Size {
-    /// Returns the size as an unsigned 8-bit integer
-    pub fn as_u8(&self) -> u8 {
-        let size = format!("{:?}", self).replace("Side", "");
-        size.parse().unwrap()
+    /// Returns the size with the given width and height, or `None` if one of them is too small or
+    /// too large, or if one of them is more than twice the other one (there would not be
+    /// enough different rows or columns to fill the grid)
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - the number of columns
+    /// * `height` - the number of rows
+    pub fn new(width: u8, height: u8) -> Option<Size> {
+        if Size::is_valid_side(width) && Size::is_valid_side(height) && width <= 2 * height && height <= 2 * width {
+            Some(Size {
+                width,
+                height,
+            })
+        } else {
+            None
+        }
     }
 
-    /// Returns the `Size` corresponding to a given string slice
+    /// Returns the square size with the given side, or `None` if the side is too small or too
+    /// large
     ///
     /// # Arguments
     ///
-    /// * `size` - a string slice representing a size
-    pub fn from_str(size: &str) -> Option<Size> {
-        let side = format!("{}x{}", size, size);
-        for a_size in all::<Size>() {
-            if format!("{}", a_size) == side {
-                return Some(a_size);
-            }
-        }
-        None
+    /// * `side` - the number of rows and of columns
+    pub fn square(side: u8) -> Option<Size> {
+        Size::new(side, side)
+    }
+
+    /// Returns the number of columns
+    pub fn width(&self) -> u8 {
+        self.width
+    }
+
+    /// Returns the number of rows
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
+    /// Returns the square sizes, from the smallest to the largest one
+    pub fn squares() -> Vec<Size> {
+        (Size::MIN_SIDE..=Size::MAX_SIDE).filter_map(Size::square).collect()
     }
+
+    /// Returns whether or not a number of rows or of columns is allowed
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - a number of rows or of columns
+    fn is_valid_side(side: u8) -> bool {
+        (Size::MIN_SIDE..=Size::MAX_SIDE).contains(&side)
+    }
+
+    pub const MIN_SIDE: u8 = 6;
+    pub const MAX_SIDE: u8 = 20;
 }
 
 impl fmt::Display for Size {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let size = self.as_u8();
-        let printable = format!("{}x{}", size, size);
-        write!(f, "{}", printable)
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+impl FromStr for Size {
+    type Err = ParseEnumError;
+
+    /// Returns the `Size` corresponding to a given string slice, either a side such as `8` or a
+    /// width and a height such as `8x12`
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - a string slice representing a size
+    fn from_str(size: &str) -> Result<Size, ParseEnumError> {
+        let result = match size.trim().split_once('x') {
+            Some((width, height)) => width.parse().ok().zip(height.parse().ok()).and_then(|(width, height)| Size::new(width, height)),
+            None => size.trim().parse().ok().and_then(Size::square),
+        };
+        result.ok_or(ParseEnumError)
+    }
+}
+
+/// The error returned when a string slice represents neither a difficulty nor a size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseEnumError;
+
+impl fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The text is neither a difficulty nor a size!")
     }
 }
 
@@ -136,3 +189,36 @@ impl Value {
     const FIRST_CHAR: char = '0';
     const SECOND_CHAR: char = '1';
 }
+
+/// The notes the player can write in a box of a binero grid without putting a value in it
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Note {
+    Probably(Value),
+    Marked,
+}
+
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Note::Probably(value) => write!(f, "{}", value),
+            Note::Marked => write!(f, "{}", Note::MARKED_CHAR),
+        }
+    }
+}
+
+impl Note {
+    /// Returns a `Some(Note)` if the given character is correct and `None` otherwise
+    ///
+    /// # Arguments
+    ///
+    /// * `note` - a character
+    pub fn from_char(note: char) -> Option<Note> {
+        match note {
+            Note::MARKED_CHAR => Some(Note::Marked),
+            _ => Value::from_char(note).map(Note::Probably),
+        }
+    }
+
+    /// The character representing a marked box
+    pub const MARKED_CHAR: char = '#';
+}
@@ -2,28 +2,46 @@
 //!
 //! `changing` represents the changing part of the GUI, used during a game
 
-use std::{cell::RefCell, collections::HashMap, fmt, path::Path, rc::Rc, sync::mpsc::Sender, thread, time::Duration};
+use std::{cell::RefCell, fmt, path::Path, rc::Rc, sync::{Arc, atomic::{AtomicBool, Ordering}, mpsc::{Sender, TryRecvError}}, thread, time::{Duration, Instant}};
 use tr::tr;
-use fltk::{button::Button, enums::{Color, Event}, prelude::{ImageExt, InputExt, WidgetBase, WidgetExt}, frame::Frame, image::SvgImage, input::Input};
-use enum_iterator::{all, last};
-use crate::engine::{Binero, history::Item};
-use crate::enums::{Difficulty, Size, Value};
-use crate::gui::{BG_COLOR, SELECT_COLOR, RO_SELECT_COLOR, display_alert, display_message, sound::Sound, timer::Timer, user_data::{UserPrefs, BestScores}};
+use fltk::{app, button::Button, enums::{Align, Color, FrameType}, prelude::{ImageExt, WidgetBase, WidgetExt}, frame::Frame, image::SvgImage};
+use yabinero::engine::{Binero, history::Item, solver::{Line, Step, Technique}};
+use yabinero::enums::{Difficulty, Note, Size, Value};
+use crate::translation::Translate;
+use crate::gui::{BG_COLOR, HINT_COLOR, NOTE_COLOR, ERROR_COLOR, CONFLICT_COLOR, LABEL_SIZE, display_alert, display_hint, display_message, scaled, generator::Generator, grid::{Entry, Grid}, sound::Sound, timer::Timer, user_data::{UserPrefs, BestScores, DailyScores, PackScores, SavedGame}};
 
 /// The changing part of the GUI, used during a game
 pub struct ChangingPart {
-    grids: HashMap<Size, Rc<RefCell<Vec<Vec<Input>>>>>,
+    grid: Grid,
     pause: Frame,
+    generating: Frame,
     timer: Rc<RefCell<Timer>>,
+    seed: Frame,
+    status: Frame,
     but_pause: Button,
     but_resume: Button,
     but_undo: Button,
     but_redo: Button,
     but_retry: Button,
     but_solve: Button,
+    but_hint: Button,
+    but_check: Button,
+    but_notes: Button,
+    but_cancel: Button,
     success: bool,
     paused: bool,
+    notes_mode: bool,
+    hints: u16,
+    hinted_box: Option<(u8, u8)>,
+    wrong_boxes: Vec<(u8, u8)>,
+    conflicts: Vec<(u8, u8, Value)>,
+    conflict_boxes: Vec<(u8, u8)>,
+    daily: Option<String>,
+    pack: Option<String>,
     binero: Option<Rc<RefCell<Binero>>>,
+    tx: Option<Sender<bool>>,
+    generator: Generator,
+    cancelled: Arc<AtomicBool>,
 }
 
 impl ChangingPart {
@@ -35,59 +53,177 @@ impl ChangingPart {
     /// * `ending_x` - the ending point for the width of the part of the GUI used during a game
     /// * `ending_y` - the ending point for the height of the part of the GUI used during a game
     pub fn new(starting_y: i32, ending_x: i32, ending_y: i32) -> ChangingPart {
-        let mut grids = HashMap::new();
-        let max_size = last::<Size>().unwrap().as_u8() as i32;
-        for size in all::<Size>() {
-            let delta_to_center = (max_size - size.as_u8() as i32) / 2 * ChangingPart::INPUT_SIZE;
-            grids.insert(size, ChangingPart::init_grid(size.as_u8(), starting_y, delta_to_center));
-        }
-        let starting_x = max_size * ChangingPart::INPUT_SIZE + ChangingPart::MARGIN_X;
+        let grid = Grid::new(0, starting_y, ChangingPart::GRID_AREA);
+        let status = ChangingPart::init_status(ending_x, ending_y);
+        let ending_y = ending_y - ChangingPart::STATUS_HEIGHT;
+        let starting_x = ChangingPart::GRID_AREA + ChangingPart::MARGIN_X;
         let width = ending_x - starting_x - ChangingPart::MARGIN_X;
         let timer = Rc::new(RefCell::new(Timer::new(starting_x, starting_y + ChangingPart::MARGIN_Y, width)));
+        let seed = ChangingPart::init_seed(starting_x, starting_y + ChangingPart::MARGIN_Y + Timer::HEIGHT, width);
         let but_pause = ChangingPart::init_button(starting_x, ending_y - ChangingPart::HEIGHT - ChangingPart::MARGIN_Y, width, PlayButton::Pause);
         let but_resume = ChangingPart::init_button(starting_x, ending_y - ChangingPart::HEIGHT - ChangingPart::MARGIN_Y, width, PlayButton::Resume);
         let but_undo = ChangingPart::init_button(starting_x, ending_y - 5 * (ChangingPart::HEIGHT + ChangingPart::MARGIN_Y), width, PlayButton::Undo);
         let but_redo = ChangingPart::init_button(starting_x, ending_y - 4 * (ChangingPart::HEIGHT + ChangingPart::MARGIN_Y), width, PlayButton::Redo);
         let but_retry = ChangingPart::init_button(starting_x, ending_y - 3 * (ChangingPart::HEIGHT + ChangingPart::MARGIN_Y), width, PlayButton::Retry);
         let but_solve = ChangingPart::init_button(starting_x, ending_y - 2 * (ChangingPart::HEIGHT + ChangingPart::MARGIN_Y), width, PlayButton::Solve);
+        let but_hint = ChangingPart::init_button(starting_x, ending_y - 6 * (ChangingPart::HEIGHT + ChangingPart::MARGIN_Y), width, PlayButton::Hint);
+        let but_check = ChangingPart::init_button(starting_x, ending_y - 7 * (ChangingPart::HEIGHT + ChangingPart::MARGIN_Y), width, PlayButton::Check);
+        let but_notes = ChangingPart::init_button(starting_x, ending_y - 8 * (ChangingPart::HEIGHT + ChangingPart::MARGIN_Y), width, PlayButton::Notes);
         let pause = ChangingPart::init_pause(starting_x, ending_y);
+        let generating = ChangingPart::init_generating(starting_y, ChangingPart::GRID_AREA);
+        let but_cancel = ChangingPart::init_button((ChangingPart::GRID_AREA - width) / 2, starting_y + ChangingPart::GRID_AREA / 2 + ChangingPart::MARGIN_Y, width, PlayButton::Cancel);
         ChangingPart {
-            grids,
+            grid,
             pause,
+            generating,
             timer,
+            seed,
+            status,
             but_pause,
             but_resume,
             but_undo,
             but_redo,
             but_retry,
             but_solve,
+            but_hint,
+            but_check,
+            but_notes,
+            but_cancel,
             success: false,
             paused: false,
+            notes_mode: false,
+            hints: 0,
+            hinted_box: None,
+            wrong_boxes: Vec::new(),
+            conflicts: Vec::new(),
+            conflict_boxes: Vec::new(),
+            daily: None,
+            pack: None,
             binero: None,
+            tx: None,
+            generator: Generator::new(),
+            cancelled: Arc::new(AtomicBool::new(false)),
         }
     }
 
-    /// Creates a new game and returns the `Sender` to pause the game
+    /// Creates a new game
+    ///
+    /// # Arguments
+    ///
+    /// * `user_prefs` - the user's preferences
+    /// * `changing` - the changing part of the GUI
+    /// * `seed` - the seed used to generate the binero or `None` to pick one randomly
+    pub fn new_game(user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>, seed: Option<u64>) {
+        let size = user_prefs.borrow().size();
+        let difficulty = user_prefs.borrow().difficulty();
+        let binero = match seed {
+            Some(_) => None,
+            None => changing.borrow().generator.take(size, difficulty),
+        };
+        match binero {
+            Some(binero) => ChangingPart::start_game(user_prefs, changing, binero, 0, 0, None, None),
+            None => ChangingPart::wait_for_binero(user_prefs, changing, size, difficulty, seed, None),
+        }
+    }
+
+    /// Creates a new game with today's daily puzzle for the size chosen by the user
+    ///
+    /// # Arguments
+    ///
+    /// * `user_prefs` - the user's preferences
+    /// * `changing` - the changing part of the GUI
+    pub fn new_daily_game(user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>) {
+        let size = user_prefs.borrow().size();
+        let today = DailyScores::today();
+        let seed = Some(DailyScores::seed(&today, size));
+        ChangingPart::wait_for_binero(user_prefs, changing, size, ChangingPart::DAILY_DIFFICULTY, seed, Some(today));
+    }
+
+    /// Generates in advance a few bineros with the size and the difficulty chosen by the user, so
+    /// that the next game starts at once
     ///
     /// # Arguments
     ///
     /// * `user_prefs` - the user's preferences
     /// * `changing` - the changing part of the GUI
-    pub fn new_game(user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>) -> Sender<bool> {
-        let binero = Rc::new(RefCell::new(Binero::new(user_prefs.borrow().size(), user_prefs.borrow().difficulty())));
-        changing.borrow_mut().binero = Some(binero);
-        let tx_result = changing.borrow_mut().timer.borrow_mut().start();
-        ChangingPart::fill(changing, user_prefs, &tx_result, user_prefs.borrow().difficulty(), &changing.borrow().timer);
-        ChangingPart::add_pause_handler(changing, user_prefs.borrow().size(), Sender::clone(&tx_result));
-        ChangingPart::add_resume_handler(changing, user_prefs.borrow().size(), Sender::clone(&tx_result));
+    pub fn warm_up(user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>) {
+        changing.borrow().generator.refill(user_prefs.borrow().size(), user_prefs.borrow().difficulty());
+    }
+
+    /// Starts a game with a binero, pausing the current game if there is one
+    ///
+    /// # Arguments
+    ///
+    /// * `user_prefs` - the user's preferences
+    /// * `changing` - the changing part of the GUI
+    /// * `binero` - a binero
+    /// * `duration` - the duration already elapsed
+    /// * `hints` - the number of hints already used
+    /// * `daily` - the date of the game if it is a daily puzzle
+    /// * `pack` - the key of the binero if it comes from a pack, as returned by `PackScores::key`
+    pub fn start_game(user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>, binero: Binero, duration: u64, hints: u16, daily: Option<String>, pack: Option<String>) {
+        ChangingPart::cancel_generation(changing);
+        let previous_tx = changing.borrow_mut().tx.take();
+        if let Some(tx) = previous_tx {
+            tx.send(true).unwrap();
+            ChangingPart::pause_game(changing);
+        }
+        ChangingPart::clear_hint(changing);
+        changing.borrow_mut().wrong_boxes.clear();
+        changing.borrow_mut().conflicts.clear();
+        changing.borrow_mut().conflict_boxes.clear();
+        changing.borrow_mut().status.set_label("");
+        ChangingPart::display_seed(changing, binero.seed());
+        changing.borrow_mut().binero = Some(Rc::new(RefCell::new(binero)));
+        let tx_result = changing.borrow_mut().timer.borrow_mut().start(duration);
+        ChangingPart::fill(changing, user_prefs, &tx_result, &changing.borrow().timer);
+        ChangingPart::add_pause_handler(changing, Sender::clone(&tx_result));
+        ChangingPart::add_resume_handler(changing, Sender::clone(&tx_result));
         ChangingPart::add_undo_handler(changing);
         ChangingPart::add_redo_handler(changing);
         ChangingPart::add_retry_handler(changing);
         ChangingPart::add_solve_handler(changing, user_prefs);
+        ChangingPart::add_hint_handler(changing, user_prefs);
+        ChangingPart::add_check_handler(changing, user_prefs);
+        ChangingPart::add_notes_handler(changing);
+        ChangingPart::set_notes_mode(changing, false);
         changing.borrow_mut().pause.hide();
         changing.borrow_mut().paused = false;
         changing.borrow_mut().success = false;
-        tx_result
+        changing.borrow_mut().hints = hints;
+        changing.borrow_mut().daily = daily;
+        changing.borrow_mut().pack = pack;
+        changing.borrow_mut().tx = Some(tx_result);
+    }
+
+    /// Returns the binero of the current game, if there is one
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    pub fn binero(changing: &Rc<RefCell<ChangingPart>>) -> Option<Rc<RefCell<Binero>>> {
+        changing.borrow().binero.clone()
+    }
+
+    /// Saves the current game if it is not finished, so that it can be resumed later, without the
+    /// values breaking a rule, which are removed first
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    pub fn save_game(changing: &Rc<RefCell<ChangingPart>>) {
+        let mut saved_game = SavedGame::new();
+        if !changing.borrow().conflicts.is_empty() {
+            ChangingPart::clear_conflicts(changing);
+        }
+        let changing = changing.borrow();
+        match &changing.binero {
+            Some(binero) if !changing.success => {
+                let duration = changing.timer.borrow().elapsed(!changing.paused);
+                saved_game.save(&binero.borrow(), duration, changing.hints, changing.daily.as_deref(), changing.pack.as_deref());
+            },
+            _ => saved_game.clear(),
+        }
     }
 
     /// Pauses the game
@@ -99,12 +235,29 @@ impl ChangingPart {
         changing.borrow_mut().paused = true;
         changing.borrow_mut().but_resume.show();
         changing.borrow_mut().but_pause.hide();
-        for (_, boxes) in &changing.borrow().grids {
-            ChangingPart::hide_selected_grid(&boxes);
+        changing.borrow_mut().grid.hide();
+    }
+
+    /// Scales the texts of the changing part like the main window
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    /// * `width` - the width of the main window
+    /// * `height` - the height of the main window
+    pub fn scale_texts(changing: &Rc<RefCell<ChangingPart>>, width: i32, height: i32) {
+        let changing = &mut *changing.borrow_mut();
+        let label_size = scaled(LABEL_SIZE, width, height);
+        for button in [&mut changing.but_pause, &mut changing.but_resume, &mut changing.but_undo, &mut changing.but_redo, &mut changing.but_retry, &mut changing.but_solve, &mut changing.but_hint, &mut changing.but_check, &mut changing.but_notes, &mut changing.but_cancel] {
+            button.set_label_size(label_size);
         }
+        changing.timer.borrow().set_label_size(label_size);
+        changing.status.set_label_size(label_size);
+        changing.seed.set_label_size(scaled(ChangingPart::SEED_SIZE, width, height));
+        changing.generating.set_label_size(scaled(ChangingPart::GENERATING_SIZE, width, height));
     }
 
-    /// Cancels the current action
+    /// Cancels the current action, or removes the values breaking a rule if there are some
     ///
     /// # Arguments
     ///
@@ -112,9 +265,10 @@ impl ChangingPart {
     pub fn undo(changing: &Rc<RefCell<ChangingPart>>) {
         let binero = changing.borrow().binero.clone().unwrap();
         if !changing.borrow().success && !changing.borrow().paused {
-            let size = binero.borrow().size();
-            if let Some(item) = binero.borrow_mut().try_to_undo() {
-                ChangingPart::set_value(&changing, size, item, true);
+            if !changing.borrow().conflicts.is_empty() {
+                ChangingPart::clear_conflicts(changing);
+            } else if let Some(item) = binero.borrow_mut().try_to_undo() {
+                ChangingPart::set_value(&changing, item, true);
             }
         }
     }
@@ -127,55 +281,274 @@ impl ChangingPart {
     pub fn redo(changing: &Rc<RefCell<ChangingPart>>) {
         let binero = changing.borrow().binero.clone().unwrap();
         if !changing.borrow().success && !changing.borrow().paused {
-            let size = binero.borrow().size();
+            ChangingPart::clear_conflicts(changing);
             if let Some(item) = binero.borrow_mut().try_to_redo() {
-                ChangingPart::set_value(&changing, size, item, false);
+                ChangingPart::set_value(&changing, item, false);
             }
         }
     }
 
-    /// Fills the grid of the game with a binero
+    /// Highlights the next box that can be deduced and explains why
     ///
     /// # Arguments
     ///
     /// * `changing` - the changing part of the GUI
     /// * `user_prefs` - the user's preferences
-    /// * `tx` - a `Sender`
+    pub fn hint(changing: &Rc<RefCell<ChangingPart>>, user_prefs: &Rc<RefCell<UserPrefs>>) {
+        let binero = changing.borrow().binero.clone();
+        if let Some(binero) = binero && !changing.borrow().success && !changing.borrow().paused {
+            ChangingPart::clear_hint(changing);
+            let hint = binero.borrow().hint();
+            match hint {
+                Some(step) => {
+                    if ChangingPart::remove_conflict(changing, step.x_axis(), step.y_axis()) {
+                        ChangingPart::highlight_conflicts(changing);
+                    }
+                    changing.borrow_mut().grid.set_background(step.x_axis(), step.y_axis(), HINT_COLOR);
+                    changing.borrow_mut().hinted_box = Some((step.x_axis(), step.y_axis()));
+                    changing.borrow_mut().hints += 1;
+                    display_hint(&ChangingPart::explanation(&step));
+                },
+                None => ChangingPart::display_error(&tr!("No hint available!"), user_prefs.borrow().sounds()),
+            }
+        }
+    }
+
+    /// Marks the boxes whose values differ from the solution and counts them with the values
+    /// breaking a rule
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    /// * `user_prefs` - the user's preferences
+    pub fn check(changing: &Rc<RefCell<ChangingPart>>, user_prefs: &Rc<RefCell<UserPrefs>>) {
+        let binero = changing.borrow().binero.clone();
+        if let Some(binero) = binero && !changing.borrow().success && !changing.borrow().paused {
+            ChangingPart::clear_hint(changing);
+            let wrong_boxes = changing.borrow().wrong_boxes.clone();
+            for (x_axis, y_axis) in wrong_boxes {
+                ChangingPart::mark_box(changing, x_axis, y_axis, false);
+            }
+            ChangingPart::highlight_conflicts(changing);
+            let errors = binero.borrow().errors();
+            let conflicts = changing.borrow().conflicts.len();
+            if errors.is_empty() && conflicts == 0 {
+                display_message(&tr!("No error so far!"));
+            } else {
+                for &(x_axis, y_axis) in &errors {
+                    ChangingPart::mark_box(changing, x_axis, y_axis, true);
+                }
+                ChangingPart::display_error(&tr!("Wrong values: {}", errors.len() + conflicts), user_prefs.borrow().sounds());
+            }
+        }
+    }
+
+    /// Switches between writing values and writing notes in the boxes
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    pub fn toggle_notes(changing: &Rc<RefCell<ChangingPart>>) {
+        if changing.borrow().binero.is_some() && !changing.borrow().success && !changing.borrow().paused {
+            let notes_mode = !changing.borrow().notes_mode;
+            ChangingPart::set_notes_mode(changing, notes_mode);
+        }
+    }
+
+    /// Sets a named checkpoint on the current state of the game
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    /// * `name` - the name of the checkpoint
+    pub fn add_checkpoint(changing: &Rc<RefCell<ChangingPart>>, name: &str) {
+        let binero = changing.borrow().binero.clone();
+        if let Some(binero) = binero && !changing.borrow().success && !changing.borrow().paused {
+            binero.borrow_mut().add_checkpoint(name);
+        }
+    }
+
+    /// Goes back to a checkpoint of the current game
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    /// * `checkpoint` - the index of the checkpoint
+    pub fn roll_back_to(changing: &Rc<RefCell<ChangingPart>>, checkpoint: usize) {
+        let binero = changing.borrow().binero.clone();
+        if let Some(binero) = binero && !changing.borrow().success && !changing.borrow().paused {
+            ChangingPart::clear_hint(changing);
+            ChangingPart::clear_conflicts(changing);
+            let items = binero.borrow_mut().roll_back_to(checkpoint);
+            for (item, undo) in &items {
+                ChangingPart::set_value(changing, item, *undo);
+            }
+        }
+    }
+
+    /// Generates a binero in the background, displays the time elapsed since the generation
+    /// started and starts a game with the binero as soon as it is received, unless the generation
+    /// is cancelled
+    ///
+    /// # Arguments
+    ///
+    /// * `user_prefs` - the user's preferences
+    /// * `changing` - the changing part of the GUI
+    /// * `size` - a size
     /// * `difficulty` - a difficulty
+    /// * `seed` - the seed used to generate the binero or `None` to pick one randomly
+    /// * `daily` - the date of the game if it is a daily puzzle
+    fn wait_for_binero(user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>, size: Size, difficulty: Difficulty, seed: Option<u64>, daily: Option<String>) {
+        ChangingPart::cancel_generation(changing);
+        ChangingPart::suspend_game(changing);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let rx = Generator::generate(size, difficulty, seed, Arc::clone(&cancelled));
+        changing.borrow_mut().cancelled = Arc::clone(&cancelled);
+        {
+            let mut changing = changing.borrow_mut();
+            changing.generating.set_label(&tr!("Generating…"));
+            changing.generating.show();
+            changing.but_cancel.show();
+        }
+        let cloned_changing = Rc::clone(changing);
+        changing.borrow_mut().but_cancel.set_callback(Box::new(move |_: &mut Button| {
+            ChangingPart::cancel_generation(&cloned_changing);
+        }));
+        let cloned_prefs = Rc::clone(user_prefs);
+        let cloned_changing = Rc::clone(changing);
+        let started = Instant::now();
+        let mut daily = daily;
+        app::add_timeout3(ChangingPart::POLLING_DELAY, move |handle| {
+            if cancelled.load(Ordering::Relaxed) {
+                return;
+            }
+            match rx.try_recv() {
+                Ok(binero) => ChangingPart::start_game(&cloned_prefs, &cloned_changing, binero, 0, 0, daily.take(), None),
+                Err(TryRecvError::Empty) => {
+                    let elapsed = Timer::format(started.elapsed().as_secs());
+                    cloned_changing.borrow_mut().generating.set_label(&tr!("Generating… {}", elapsed));
+                    app::repeat_timeout3(ChangingPart::POLLING_DELAY, handle);
+                },
+                Err(TryRecvError::Disconnected) => ChangingPart::cancel_generation(&cloned_changing),
+            }
+        });
+    }
+
+    /// Cancels the generation of a binero, if there is one, which stops its thread, and shows again
+    /// the grid of a finished game
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    fn cancel_generation(changing: &Rc<RefCell<ChangingPart>>) {
+        if !changing.borrow().generating.visible() {
+            return;
+        }
+        {
+            let mut changing = changing.borrow_mut();
+            changing.cancelled.store(true, Ordering::Relaxed);
+            changing.generating.hide();
+            changing.but_cancel.hide();
+        }
+        if changing.borrow().binero.is_some() && changing.borrow().success {
+            changing.borrow_mut().grid.show();
+        }
+    }
+
+    /// Pauses the current game, if it is running, and hides the grid while a binero is generated
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    fn suspend_game(changing: &Rc<RefCell<ChangingPart>>) {
+        let running = {
+            let changing = changing.borrow();
+            changing.binero.is_some() && !changing.success && !changing.paused
+        };
+        if running {
+            if let Some(tx) = &changing.borrow().tx {
+                tx.send(true).unwrap();
+            }
+            ChangingPart::pause_game(changing);
+        } else {
+            changing.borrow_mut().grid.hide();
+        }
+    }
+
+    /// Fills the grid of the game with a binero and shows it
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    /// * `user_prefs` - the user's preferences
+    /// * `tx` - a `Sender`
     /// * `timer` - a timer
-    fn fill(changing: &Rc<RefCell<ChangingPart>>, user_prefs: &Rc<RefCell<UserPrefs>>, tx: &Sender<bool>, difficulty: Difficulty, timer: &Rc<RefCell<Timer>>) {
+    fn fill(changing: &Rc<RefCell<ChangingPart>>, user_prefs: &Rc<RefCell<UserPrefs>>, tx: &Sender<bool>, timer: &Rc<RefCell<Timer>>) {
         let binero = changing.borrow().binero.clone().unwrap();
         let size = binero.borrow().size();
-        for (a_size, boxes) in &changing.borrow().grids {
-            if *a_size == size {
-                ChangingPart::fill_selected_grid(&boxes, user_prefs, tx, difficulty, timer, changing);
-            } else {
-                ChangingPart::hide_selected_grid(&boxes);
+        {
+            let mut changing = changing.borrow_mut();
+            changing.grid.set_size(size);
+            changing.grid.set_colors(user_prefs.borrow().color(), user_prefs.borrow().ro_color());
+            for x_axis in 0..size.height() {
+                for y_axis in 0..size.width() {
+                    let value = binero.borrow().get(x_axis, y_axis);
+                    let note = binero.borrow().note(x_axis, y_axis);
+                    changing.grid.set_box(x_axis, y_axis, value, note);
+                    changing.grid.set_given(x_axis, y_axis, binero.borrow().is_given(x_axis, y_axis));
+                    changing.grid.set_background(x_axis, y_axis, ChangingPart::background(&value, &note));
+                }
             }
+            changing.grid.show();
         }
+        ChangingPart::add_entry_handler(changing, user_prefs, tx, timer);
     }
 
-    /// Returns a grid
+    /// Returns the `Frame` displaying the seed of the current binero
     ///
     /// # Arguments
     ///
-    /// * `size` - a size
-    /// * `starting_y` - the starting point for the height of the grid in the GUI
-    /// * `delta_to_center` - the delta to center the grid
-    fn init_grid(size: u8, starting_y: i32, delta_to_center: i32) -> Rc<RefCell<Vec<Vec<Input>>>> {
-        let mut boxes = Vec::new();
-        for i in 0..size {
-            boxes.push(Vec::new());
-            for j in 0..size {
-                let mut input = Input::new(j as i32 * ChangingPart::INPUT_SIZE + delta_to_center,
-                                           starting_y + i as i32 * ChangingPart::INPUT_SIZE + delta_to_center,
-                                           ChangingPart::INPUT_SIZE, ChangingPart::INPUT_SIZE, "");
-                input.set_text_size(20);
-                input.hide();
-                boxes[i as usize].push(input);
-            }
+    /// * `x` - the x-axis of the frame
+    /// * `y` - the y-axis of the frame
+    /// * `width` - the width of the frame
+    fn init_seed(x: i32, y: i32, width: i32) -> Frame {
+        let mut seed = Frame::new(x, y, width, ChangingPart::SEED_HEIGHT, "");
+        seed.set_label_size(ChangingPart::SEED_SIZE);
+        seed.hide();
+        seed
+    }
+
+    /// Displays the seed of the current binero, if it has one
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    /// * `seed` - the seed of the binero
+    fn display_seed(changing: &Rc<RefCell<ChangingPart>>, seed: Option<u64>) {
+        let mut changing = changing.borrow_mut();
+        match seed {
+            Some(seed) => {
+                changing.seed.set_label(&tr!("Seed: {}", seed));
+                changing.seed.show();
+            },
+            None => changing.seed.hide(),
         }
-        Rc::new(RefCell::new(boxes))
+    }
+
+    /// Returns the `Frame` of the status bar, at the bottom of the window
+    ///
+    /// # Arguments
+    ///
+    /// * `ending_x` - the ending point for the width of the part of the GUI used during a game
+    /// * `ending_y` - the ending point for the height of the part of the GUI used during a game
+    fn init_status(ending_x: i32, ending_y: i32) -> Frame {
+        let mut status = Frame::new(0, ending_y - ChangingPart::STATUS_HEIGHT, ending_x, ChangingPart::STATUS_HEIGHT, "");
+        status.set_frame(FrameType::FlatBox);
+        status.set_color(BG_COLOR);
+        status.set_label_color(Color::DarkRed);
+        status.set_label_size(LABEL_SIZE);
+        status.set_align(Align::Left | Align::Inside);
+        status
     }
 
     /// Returns the `Frame` displayed when the game is paused
@@ -194,6 +567,19 @@ impl ChangingPart {
         pause
     }
 
+    /// Returns the `Frame` displayed while a binero is generated
+    ///
+    /// # Arguments
+    ///
+    /// * `starting_y` - the starting point for the height of the grid in the GUI
+    /// * `grid_size` - the width and the height of the largest grid
+    fn init_generating(starting_y: i32, grid_size: i32) -> Frame {
+        let mut generating = Frame::new(0, starting_y, grid_size, grid_size / 2, "");
+        generating.set_label_size(ChangingPart::GENERATING_SIZE);
+        generating.hide();
+        generating
+    }
+
     /// Returns a button
     ///
     /// # Arguments
@@ -210,153 +596,194 @@ impl ChangingPart {
         button
     }
 
-    /// Hides the selected grid
+    /// Returns the background color of a box, showing whether or not it is marked
     ///
     /// # Arguments
     ///
-    /// * `boxes` - a grid
-    fn hide_selected_grid(boxes: &Rc<RefCell<Vec<Vec<Input>>>>) {
-        let size = boxes.borrow().len();
-        for i in 0..size {
-            for j in 0..size {
-                let input = &mut boxes.borrow_mut()[i][j];
-                input.hide();
-            }
+    /// * `value` - a value
+    /// * `note` - a note
+    fn background(value: &Option<Value>, note: &Option<Note>) -> Color {
+        if value.is_none() && *note == Some(Note::Marked) {
+            NOTE_COLOR
+        } else {
+            Color::BackGround2
         }
     }
 
-    /// Shows the selected grid
+    /// Adds the handler of what the player does in the boxes of the grid
     ///
     /// # Arguments
     ///
-    /// * `boxes` - a grid
-    fn show_selected_grid(boxes: &Rc<RefCell<Vec<Vec<Input>>>>) {
-        let size = boxes.borrow().len();
-        for i in 0..size {
-            for j in 0..size {
-                let input = &mut boxes.borrow_mut()[i][j];
-                input.show();
+    /// * `changing` - the changing part of the GUI
+    /// * `user_prefs` - the user's preferences
+    /// * `tx` - a `Sender`
+    /// * `timer` - a timer
+    fn add_entry_handler(changing: &Rc<RefCell<ChangingPart>>, user_prefs: &Rc<RefCell<UserPrefs>>, tx: &Sender<bool>, timer: &Rc<RefCell<Timer>>) {
+        let cloned_changing = Rc::clone(changing);
+        let cloned_prefs = Rc::clone(user_prefs);
+        let cloned_tx = Sender::clone(tx);
+        let cloned_timer = Rc::clone(timer);
+        changing.borrow_mut().grid.set_entry_handler(move |x_axis: u8, y_axis: u8, entry: Entry| {
+            let binero = cloned_changing.borrow().binero.clone().unwrap();
+            let value = ChangingPart::conflict(&cloned_changing, x_axis, y_axis).or(binero.borrow().get(x_axis, y_axis));
+            let note = binero.borrow().note(x_axis, y_axis);
+            let notes_mode = cloned_changing.borrow().notes_mode;
+            let val = match entry {
+                Entry::Typed(c) => c.to_string(),
+                Entry::Emptied => String::new(),
+                Entry::Clicked { .. } if notes_mode && value.is_some() => return,
+                Entry::Clicked { backwards } => ChangingPart::clicked_text(&value, &note, notes_mode, backwards).to_owned(),
+            };
+            let written = ChangingPart::write_box(&cloned_changing, x_axis, y_axis, &val, &cloned_prefs, &cloned_tx, &cloned_timer);
+            if written && matches!(entry, Entry::Typed(_)) && cloned_prefs.borrow().auto_advance() {
+                let next_box = ChangingPart::next_empty_box(&binero.borrow(), x_axis, y_axis);
+                if let Some((x, y)) = next_box {
+                    cloned_changing.borrow_mut().grid.move_cursor(x, y);
+                }
             }
-        }
+        });
     }
 
-    /// Fills the selected grid
+    /// Writes in the binero what the player typed in a box, as a value or, in notes mode, as a
+    /// note, and returns whether or not a value was put without completing the grid
+    ///
+    /// When the conflicts are highlighted, a value breaking a rule is kept in the box instead of
+    /// being refused, until it can be put or the box is changed.
     ///
     /// # Arguments
     ///
-    /// * `boxes` - a grid
+    /// * `changing` - the changing part of the GUI
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `val` - the typed text
     /// * `user_prefs` - the user's preferences
     /// * `tx` - a `Sender`
-    /// * `difficulty` - a difficulty
     /// * `timer` - a timer
-    /// * `changing` - the changing part of the GUI
-    fn fill_selected_grid(boxes: &Rc<RefCell<Vec<Vec<Input>>>>, user_prefs: &Rc<RefCell<UserPrefs>>, tx: &Sender<bool>, difficulty: Difficulty, timer: &Rc<RefCell<Timer>>, changing: &Rc<RefCell<ChangingPart>>) {
+    fn write_box(changing: &Rc<RefCell<ChangingPart>>, x_axis: u8, y_axis: u8, val: &str, user_prefs: &Rc<RefCell<UserPrefs>>, tx: &Sender<bool>, timer: &Rc<RefCell<Timer>>) -> bool {
         let binero = changing.borrow().binero.clone().unwrap();
-        let size = boxes.borrow().len();
-        for i in 0..size {
-            for j in 0..size {
-                let input = &mut boxes.borrow_mut()[i][j];
-                ChangingPart::fill_box(input, &binero, i as u8, j as u8, user_prefs.borrow().color(), user_prefs.borrow().ro_color());
-                ChangingPart::add_event_handler(boxes, input, i as u8, j as u8, user_prefs, tx, difficulty, timer, changing);
+        let removed = ChangingPart::remove_conflict(changing, x_axis, y_axis);
+        let old_value = binero.borrow().get(x_axis, y_axis);
+        let note = binero.borrow().note(x_axis, y_axis);
+        if changing.borrow().notes_mode {
+            ChangingPart::write_note(changing, x_axis, y_axis, val, user_prefs.borrow().sounds());
+            ChangingPart::resolve_conflicts(changing, user_prefs);
+            return false;
+        }
+        if changing.borrow().hinted_box == Some((x_axis, y_axis)) && old_value.is_none() && !val.is_empty() {
+            ChangingPart::clear_hint(changing);
+        }
+        let mut written = false;
+        if val == "0" || val == "1" {
+            let val = Value::from_u8(val.parse().unwrap());
+            if old_value != val {
+                if binero.borrow_mut().try_to_put(x_axis, y_axis, val) {
+                    changing.borrow_mut().grid.set_box(x_axis, y_axis, val, note);
+                    let wrong = user_prefs.borrow().check_immediately() && binero.borrow().is_wrong(x_axis, y_axis);
+                    ChangingPart::mark_box(changing, x_axis, y_axis, wrong);
+                    written = true;
+                } else if user_prefs.borrow().highlight_conflicts() {
+                    ChangingPart::add_conflict(changing, x_axis, y_axis, val.unwrap(), user_prefs.borrow().sounds());
+                } else {
+                    ChangingPart::display_error(&tr!("Bad value!"), user_prefs.borrow().sounds());
+                }
             }
+        } else if val.is_empty() {
+            if old_value.is_some() {
+                binero.borrow_mut().try_to_put(x_axis, y_axis, None);
+                changing.borrow_mut().grid.set_box(x_axis, y_axis, None, note);
+                ChangingPart::mark_box(changing, x_axis, y_axis, false);
+            } else if note.is_some() && !removed {
+                binero.borrow_mut().try_to_note(x_axis, y_axis, None);
+                changing.borrow_mut().grid.set_box(x_axis, y_axis, None, None);
+                ChangingPart::mark_box(changing, x_axis, y_axis, false);
+            }
+        } else {
+            ChangingPart::display_error(&tr!("Bad value!"), user_prefs.borrow().sounds());
+        }
+        ChangingPart::resolve_conflicts(changing, user_prefs);
+        if written && binero.borrow().is_full() {
+            tx.send(true).unwrap();
+            ChangingPart::display_success(user_prefs.borrow().sounds(), binero.borrow().size(), binero.borrow().difficulty(), timer, changing);
+            return false;
         }
+        written
     }
 
-    /// Fills a box
+    /// Returns the text written in a box when the player clicks on it: the next value, cycling
+    /// through no value, `0` and `1`, or, in notes mode, the next note, cycling through no note,
+    /// `0`, `1` and the mark
     ///
     /// # Arguments
     ///
-    /// * `input` - a box
-    /// * `value` - a value
-    fn fill_box_with_value(input: &mut Input, value: &Option<Value>) {
-        let val = if let Some(v) = value {
-            format!(" {}", v)
+    /// * `value` - the value of the box
+    /// * `note` - the note of the box
+    /// * `notes_mode` - whether or not the player writes notes
+    /// * `backwards` - whether or not the cycle is followed backwards
+    fn clicked_text(value: &Option<Value>, note: &Option<Note>, notes_mode: bool, backwards: bool) -> &'static str {
+        let (texts, current): (&[&str], usize) = if notes_mode {
+            (&["", "0", "1", "#"], match note {
+                None => 0,
+                Some(Note::Probably(Value::First)) => 1,
+                Some(Note::Probably(Value::Second)) => 2,
+                Some(Note::Marked) => 3,
+            })
         } else {
-            String::from(" ")
+            (&["", "0", "1"], match value {
+                None => 0,
+                Some(Value::First) => 1,
+                Some(Value::Second) => 2,
+            })
         };
-        input.set_value(&val);
+        let next = if backwards {
+            current + texts.len() - 1
+        } else {
+            current + 1
+        };
+        texts[next % texts.len()]
     }
 
-    /// Fills a box
+    /// Returns the first box without value after a box, going through the rows and coming back
+    /// to the first box after the last one, or `None` if the grid is full
     ///
     /// # Arguments
     ///
-    /// * `input` - a box
     /// * `binero` - a binero
     /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
     /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
-    /// * `color` - the color of the writable boxes
-    /// * `ro_ color` - the color of the read-only boxes
-    fn fill_box(input: &mut Input, binero: &Rc<RefCell<Binero>>, x_axis: u8, y_axis: u8, color: Color, ro_color: Color) {
-        let value = binero.borrow().get(x_axis, y_axis);
-        ChangingPart::fill_box_with_value(input, &value);
-        if value.is_some() {
-            input.set_readonly(true);
-            input.set_text_color(ro_color);
-            input.set_selection_color(RO_SELECT_COLOR);
-        } else {
-            input.set_readonly(false);
-            input.set_text_color(color);
-            input.set_selection_color(SELECT_COLOR);
-        }
-        input.show();
+    fn next_empty_box(binero: &Binero, x_axis: u8, y_axis: u8) -> Option<(u8, u8)> {
+        let width = binero.size().width() as usize;
+        let squares = width * binero.size().height() as usize;
+        let start = x_axis as usize * width + y_axis as usize;
+        (1..squares).map(|k| (start + k) % squares).map(|k| ((k / width) as u8, (k % width) as u8)).find(|&(x, y)| binero.get(x, y).is_none())
     }
 
-    /// Adds the event handler to the box
+    /// Writes in a box the note typed by the player: `0` or `1` for a probable value, `#` to mark
+    /// the box or remove its mark, nothing to remove the note
     ///
     /// # Arguments
     ///
-    /// * `boxes` - a grid
-    /// * `input` - a box
+    /// * `changing` - the changing part of the GUI
     /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
     /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
-    /// * `user_prefs` - the user's preferences
-    /// * `tx` - a `Sender`
-    /// * `difficulty` - a difficulty
-    /// * `timer` - a timer
-    /// * `changing` - the changing part of the GUI
-    fn add_event_handler(boxes: &Rc<RefCell<Vec<Vec<Input>>>>, input: &mut Input, x_axis: u8, y_axis: u8, user_prefs: &Rc<RefCell<UserPrefs>>, tx: &Sender<bool>, difficulty: Difficulty, timer: &Rc<RefCell<Timer>>, changing: &Rc<RefCell<ChangingPart>>) {
+    /// * `val` - the typed text
+    /// * `sounds` - whether or not the sounds must be played
+    fn write_note(changing: &Rc<RefCell<ChangingPart>>, x_axis: u8, y_axis: u8, val: &str, sounds: bool) {
         let binero = changing.borrow().binero.clone().unwrap();
-        let cloned_boxes = Rc::clone(boxes);
-        let cloned_binero = Rc::clone(&binero);
-        let cloned_prefs = Rc::clone(user_prefs);
-        let cloned_tx = Sender::clone(tx);
-        let cloned_timer = Rc::clone(timer);
-        let cloned_changing = Rc::clone(changing);
-        input.handle(Box::new(move |_: &mut Input, ev: Event| {
-            match ev {
-                Event::KeyUp | Event::Unfocus => {
-                    let old_value = cloned_binero.borrow().get(x_axis, y_axis);
-                    let value = cloned_boxes.borrow()[x_axis as usize][y_axis as usize].value();
-                    let val = value.trim();
-                    if val == "0" || val == "1" {
-                        let val = Value::from_u8(val.parse().unwrap());
-                        if old_value != val {
-                            if cloned_binero.borrow_mut().try_to_put(x_axis, y_axis, val) {
-                                ChangingPart::fill_box_with_value(&mut cloned_boxes.borrow_mut()[x_axis as usize][y_axis as usize], &val);
-                                if cloned_binero.borrow().is_full() {
-                                    cloned_tx.send(true).unwrap();
-                                    ChangingPart::display_success(cloned_prefs.borrow().sounds(), cloned_binero.borrow().size(), difficulty, &cloned_timer, &cloned_changing);
-                                }
-                            } else {
-                                ChangingPart::fill_box_with_value(&mut cloned_boxes.borrow_mut()[x_axis as usize][y_axis as usize], &old_value);
-                                ChangingPart::display_error(&tr!("Bad value!"), cloned_prefs.borrow().sounds());
-                            }
-                        }
-                    } else if val == "" {
-                        if old_value.is_some() {
-                            ChangingPart::fill_box_with_value(&mut cloned_boxes.borrow_mut()[x_axis as usize][y_axis as usize], &None);
-                            cloned_binero.borrow_mut().try_to_put(x_axis, y_axis, None);
-                        }
-                    } else {
-                        ChangingPart::fill_box_with_value(&mut cloned_boxes.borrow_mut()[x_axis as usize][y_axis as usize], &old_value);
-                        ChangingPart::display_error(&tr!("Bad value!"), cloned_prefs.borrow().sounds());
-                    }
-                    true
-                },
-                _ => false,
-            }
-        }));
+        let value = binero.borrow().get(x_axis, y_axis);
+        let old_note = binero.borrow().note(x_axis, y_axis);
+        let note = match val {
+            "0" | "1" if value.is_none() => Value::from_u8(val.parse().unwrap()).map(Note::Probably),
+            "#" if value.is_none() && old_note == Some(Note::Marked) => None,
+            "#" if value.is_none() => Some(Note::Marked),
+            "" if value.is_none() => None,
+            _ => {
+                ChangingPart::display_error(&tr!("Bad note!"), sounds);
+                return;
+            },
+        };
+        binero.borrow_mut().try_to_note(x_axis, y_axis, note);
+        changing.borrow_mut().grid.set_box(x_axis, y_axis, value, note);
+        ChangingPart::mark_box(changing, x_axis, y_axis, false);
     }
 
     /// Displays a popup with an error message and play the error sound if sounds are activated
@@ -388,8 +815,12 @@ impl ChangingPart {
         if sounds {
             Sound::Success.play();
         }
-        let mut best_scores = BestScores::new();
-        best_scores.add_best_score(size, difficulty, timer);
+        let hints = changing.borrow().hints;
+        match (&changing.borrow().daily, &changing.borrow().pack) {
+            (Some(date), _) => DailyScores::new().add_daily_score(size, date, timer, hints),
+            (None, Some(key)) => PackScores::new().add_pack_score(key, timer, hints),
+            (None, None) => BestScores::new().add_best_score(size, difficulty, timer, hints),
+        }
         timer.borrow().refresh_duration();
         display_message(&tr!("Congratulations, you won!"));
     }
@@ -399,9 +830,8 @@ impl ChangingPart {
     /// # Arguments
     ///
     /// * `changing` - the changing part of the GUI
-    /// * `size` - a size
     /// * `tx` - a `Sender`
-    fn add_pause_handler(changing: &Rc<RefCell<ChangingPart>>, size: Size, tx: Sender<bool>) {
+    fn add_pause_handler(changing: &Rc<RefCell<ChangingPart>>, tx: Sender<bool>) {
         let cloned_changing = Rc::clone(changing);
         cloned_changing.borrow_mut().but_pause.show();
         changing.borrow_mut().but_pause.set_callback(Box::new(move |_: &mut Button| {
@@ -410,9 +840,7 @@ impl ChangingPart {
                 cloned_changing.borrow_mut().paused = true;
                 cloned_changing.borrow_mut().but_resume.show();
                 cloned_changing.borrow_mut().but_pause.hide();
-                if let Some(boxes) = cloned_changing.borrow().grids.get(&size) {
-                    ChangingPart::hide_selected_grid(&boxes);
-                }
+                cloned_changing.borrow_mut().grid.hide();
                 cloned_changing.borrow_mut().pause.show();
             }
         }));
@@ -423,9 +851,8 @@ impl ChangingPart {
     /// # Arguments
     ///
     /// * `changing` - the changing part of the GUI
-    /// * `size` - a size
     /// * `tx` - a `Sender`
-    fn add_resume_handler(changing: &Rc<RefCell<ChangingPart>>, size: Size, tx: Sender<bool>) {
+    fn add_resume_handler(changing: &Rc<RefCell<ChangingPart>>, tx: Sender<bool>) {
         let cloned_changing = Rc::clone(changing);
         cloned_changing.borrow_mut().but_resume.hide();
         changing.borrow_mut().but_resume.set_callback(Box::new(move |_: &mut Button| {
@@ -433,9 +860,7 @@ impl ChangingPart {
             cloned_changing.borrow_mut().paused = false;
             cloned_changing.borrow_mut().but_pause.show();
             cloned_changing.borrow_mut().but_resume.hide();
-            if let Some(boxes) = cloned_changing.borrow().grids.get(&size) {
-                ChangingPart::show_selected_grid(&boxes);
-            }
+            cloned_changing.borrow_mut().grid.show();
             cloned_changing.borrow_mut().pause.hide();
         }));
     }
@@ -445,24 +870,21 @@ impl ChangingPart {
     /// # Arguments
     ///
     /// * `changing` - the changing part of the GUI
-    /// * `size` - a size
     /// * `item` - an item of the history
     /// * `undo` - whether or not the operation is undo
-    fn set_value(changing: &Rc<RefCell<ChangingPart>>, size: Size, item: &Item, undo: bool) {
-        let mut select_boxes: Option<&Rc<RefCell<Vec<Vec<Input>>>>> = None;
-        let grids = &changing.borrow().grids;
-        for (a_size, boxes) in grids {
-            if *a_size == size {
-                select_boxes = Some(boxes);
-            }
-        }
-        let boxes = select_boxes.unwrap();
-        let value = if undo {
-            item.old_value()
+    fn set_value(changing: &Rc<RefCell<ChangingPart>>, item: &Item, undo: bool) {
+        let (value, note) = if undo {
+            (item.old_value(), item.old_note())
         } else {
-            item.new_value()
+            (item.new_value(), item.new_note())
         };
-        ChangingPart::fill_box_with_value(&mut boxes.borrow_mut()[item.x_axis() as usize][item.y_axis() as usize], &value);
+        let a_box = (item.x_axis(), item.y_axis());
+        let mut changing = changing.borrow_mut();
+        changing.grid.set_box(a_box.0, a_box.1, value, note);
+        if changing.wrong_boxes.contains(&a_box) || item.old_note() != item.new_note() || note == Some(Note::Marked) {
+            changing.grid.set_background(a_box.0, a_box.1, ChangingPart::background(&value, &note));
+        }
+        changing.wrong_boxes.retain(|&wrong_box| wrong_box != a_box);
     }
 
     /// Adds the handler to the Undo button
@@ -502,9 +924,9 @@ impl ChangingPart {
         let cloned_changing = Rc::clone(changing);
         changing.borrow_mut().but_retry.set_callback(Box::new(move |_: &mut Button| {
             if !cloned_changing.borrow().success && !cloned_changing.borrow().paused {
-                let size = binero.borrow().size();
+                ChangingPart::clear_conflicts(&cloned_changing);
                 while let Some(item) = binero.borrow_mut().try_to_undo() {
-                    ChangingPart::set_value(&cloned_changing, size, item, true);
+                    ChangingPart::set_value(&cloned_changing, item, true);
                 }
                 binero.borrow_mut().clear_history();
             }
@@ -525,13 +947,13 @@ impl ChangingPart {
         let cloned_prefs = Rc::clone(user_prefs);
         changing.borrow_mut().but_solve.set_callback(Box::new(move |_: &mut Button| {
             if !cloned_changing.borrow().success && !cloned_changing.borrow().paused {
-                let size = binero.borrow().size();
+                ChangingPart::clear_conflicts(&cloned_changing);
                 let result = binero.borrow_mut().try_to_solve();
                 while let Some(item) = binero.borrow_mut().try_to_undo() {
-                    ChangingPart::set_value(&cloned_changing, size, item, true);
+                    ChangingPart::set_value(&cloned_changing, item, true);
                 }
                 while let Some(item) = binero.borrow_mut().try_to_redo() {
-                    ChangingPart::set_value(&cloned_changing, size, item, false);
+                    ChangingPart::set_value(&cloned_changing, item, false);
                 }
                 if !result {
                     ChangingPart::display_error(&tr!("No solution!"), cloned_prefs.borrow().sounds());
@@ -540,8 +962,273 @@ impl ChangingPart {
         }));
     }
 
-    const INPUT_SIZE: i32 = 32;
-    const HEIGHT: i32 = 60;
+    /// Adds the handler to the Hint button
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    /// * `user_prefs` - the user's preferences
+    fn add_hint_handler(changing: &Rc<RefCell<ChangingPart>>, user_prefs: &Rc<RefCell<UserPrefs>>) {
+        changing.borrow_mut().but_hint.show();
+        let cloned_changing = Rc::clone(changing);
+        let cloned_prefs = Rc::clone(user_prefs);
+        changing.borrow_mut().but_hint.set_callback(Box::new(move |_: &mut Button| {
+            ChangingPart::hint(&cloned_changing, &cloned_prefs);
+        }));
+    }
+
+    /// Adds the handler to the Check button
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    /// * `user_prefs` - the user's preferences
+    fn add_check_handler(changing: &Rc<RefCell<ChangingPart>>, user_prefs: &Rc<RefCell<UserPrefs>>) {
+        changing.borrow_mut().but_check.show();
+        let cloned_changing = Rc::clone(changing);
+        let cloned_prefs = Rc::clone(user_prefs);
+        changing.borrow_mut().but_check.set_callback(Box::new(move |_: &mut Button| {
+            ChangingPart::check(&cloned_changing, &cloned_prefs);
+        }));
+    }
+
+    /// Adds the handler to the Notes button
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    fn add_notes_handler(changing: &Rc<RefCell<ChangingPart>>) {
+        changing.borrow_mut().but_notes.show();
+        let cloned_changing = Rc::clone(changing);
+        changing.borrow_mut().but_notes.set_callback(Box::new(move |_: &mut Button| {
+            ChangingPart::toggle_notes(&cloned_changing);
+        }));
+    }
+
+    /// Sets whether the player writes values or notes in the boxes, and shows it on the Notes
+    /// button
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    /// * `notes_mode` - whether or not the player writes notes
+    fn set_notes_mode(changing: &Rc<RefCell<ChangingPart>>, notes_mode: bool) {
+        let mut changing = changing.borrow_mut();
+        changing.notes_mode = notes_mode;
+        changing.but_notes.set_color(if notes_mode { NOTE_COLOR } else { BG_COLOR });
+        changing.but_notes.redraw();
+    }
+
+    /// Marks a box as wrong or removes that mark
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `wrong` - whether or not the box is wrong
+    fn mark_box(changing: &Rc<RefCell<ChangingPart>>, x_axis: u8, y_axis: u8, wrong: bool) {
+        let binero = changing.borrow().binero.clone().unwrap();
+        let color = if wrong {
+            ERROR_COLOR
+        } else {
+            ChangingPart::background(&binero.borrow().get(x_axis, y_axis), &binero.borrow().note(x_axis, y_axis))
+        };
+        changing.borrow_mut().grid.set_background(x_axis, y_axis, color);
+        let wrong_boxes = &mut changing.borrow_mut().wrong_boxes;
+        wrong_boxes.retain(|&a_box| a_box != (x_axis, y_axis));
+        if wrong {
+            wrong_boxes.push((x_axis, y_axis));
+        }
+    }
+
+    /// Returns the value breaking a rule kept in a box, if there is one
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    fn conflict(changing: &Rc<RefCell<ChangingPart>>, x_axis: u8, y_axis: u8) -> Option<Value> {
+        changing.borrow().conflicts.iter().find(|&&(x, y, _)| (x, y) == (x_axis, y_axis)).map(|&(_, _, value)| value)
+    }
+
+    /// Keeps in a box a value breaking a rule, instead of the value of the binero, and plays the
+    /// error sound if sounds are activated
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `value` - the value breaking a rule
+    /// * `sounds` - whether or not the sounds must be played
+    fn add_conflict(changing: &Rc<RefCell<ChangingPart>>, x_axis: u8, y_axis: u8, value: Value, sounds: bool) {
+        let binero = changing.borrow().binero.clone().unwrap();
+        if binero.borrow().get(x_axis, y_axis).is_some() {
+            binero.borrow_mut().try_to_put(x_axis, y_axis, None);
+        }
+        ChangingPart::mark_box(changing, x_axis, y_axis, false);
+        let note = binero.borrow().note(x_axis, y_axis);
+        changing.borrow_mut().grid.set_box(x_axis, y_axis, Some(value), note);
+        changing.borrow_mut().conflicts.push((x_axis, y_axis, value));
+        if sounds {
+            Sound::Error.play();
+        }
+    }
+
+    /// Removes the value breaking a rule kept in a box and returns whether or not there was one
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    fn remove_conflict(changing: &Rc<RefCell<ChangingPart>>, x_axis: u8, y_axis: u8) -> bool {
+        let position = changing.borrow().conflicts.iter().position(|&(x, y, _)| (x, y) == (x_axis, y_axis));
+        if let Some(position) = position {
+            let binero = changing.borrow().binero.clone().unwrap();
+            let mut changing = changing.borrow_mut();
+            changing.conflicts.remove(position);
+            changing.grid.set_box(x_axis, y_axis, binero.borrow().get(x_axis, y_axis), binero.borrow().note(x_axis, y_axis));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Removes all the values breaking a rule and their highlighting
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    fn clear_conflicts(changing: &Rc<RefCell<ChangingPart>>) {
+        let binero = changing.borrow().binero.clone().unwrap();
+        let conflicts = std::mem::take(&mut changing.borrow_mut().conflicts);
+        for (x_axis, y_axis, _) in conflicts {
+            changing.borrow_mut().grid.set_box(x_axis, y_axis, binero.borrow().get(x_axis, y_axis), binero.borrow().note(x_axis, y_axis));
+        }
+        ChangingPart::highlight_conflicts(changing);
+    }
+
+    /// Puts in the binero the values breaking a rule that no longer break it, then highlights the
+    /// remaining ones
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    /// * `user_prefs` - the user's preferences
+    fn resolve_conflicts(changing: &Rc<RefCell<ChangingPart>>, user_prefs: &Rc<RefCell<UserPrefs>>) {
+        let binero = changing.borrow().binero.clone().unwrap();
+        let conflicts = std::mem::take(&mut changing.borrow_mut().conflicts);
+        for (x_axis, y_axis, value) in conflicts {
+            let put = binero.borrow_mut().try_to_put(x_axis, y_axis, Some(value));
+            if put {
+                let wrong = user_prefs.borrow().check_immediately() && binero.borrow().is_wrong(x_axis, y_axis);
+                ChangingPart::mark_box(changing, x_axis, y_axis, wrong);
+            } else {
+                changing.borrow_mut().conflicts.push((x_axis, y_axis, value));
+            }
+        }
+        ChangingPart::highlight_conflicts(changing);
+    }
+
+    /// Highlights the values breaking a rule with the rows and the columns where they break it,
+    /// removes the previous highlighting and tells in the status bar how many values break a rule
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    fn highlight_conflicts(changing: &Rc<RefCell<ChangingPart>>) {
+        let binero = changing.borrow().binero.clone().unwrap();
+        let binero = binero.borrow();
+        let changing = &mut *changing.borrow_mut();
+        for (x_axis, y_axis) in std::mem::take(&mut changing.conflict_boxes) {
+            let color = if changing.wrong_boxes.contains(&(x_axis, y_axis)) {
+                ERROR_COLOR
+            } else if changing.hinted_box == Some((x_axis, y_axis)) {
+                HINT_COLOR
+            } else {
+                ChangingPart::background(&binero.get(x_axis, y_axis), &binero.note(x_axis, y_axis))
+            };
+            changing.grid.set_background(x_axis, y_axis, color);
+        }
+        let size = binero.size();
+        for &(x_axis, y_axis, value) in &changing.conflicts {
+            if binero.grid().breaks_row(x_axis, y_axis, value) {
+                changing.conflict_boxes.extend((0..size.width()).map(|y| (x_axis, y)));
+            }
+            if binero.grid().breaks_column(x_axis, y_axis, value) {
+                changing.conflict_boxes.extend((0..size.height()).map(|x| (x, y_axis)));
+            }
+            changing.conflict_boxes.push((x_axis, y_axis));
+        }
+        for &(x_axis, y_axis) in &changing.conflict_boxes {
+            changing.grid.set_background(x_axis, y_axis, CONFLICT_COLOR);
+        }
+        for &(x_axis, y_axis, _) in &changing.conflicts {
+            changing.grid.set_background(x_axis, y_axis, ERROR_COLOR);
+        }
+        let status = match changing.conflicts.len() {
+            0 => String::new(),
+            number => tr!("Values breaking a rule: {}", number),
+        };
+        changing.status.set_label(&status);
+        changing.status.redraw();
+    }
+
+    /// Removes the highlighting of the box given by the latest hint
+    ///
+    /// # Arguments
+    ///
+    /// * `changing` - the changing part of the GUI
+    fn clear_hint(changing: &Rc<RefCell<ChangingPart>>) {
+        let hinted_box = changing.borrow_mut().hinted_box.take();
+        let binero = changing.borrow().binero.clone();
+        if let (Some((x_axis, y_axis)), Some(binero)) = (hinted_box, binero) {
+            let color = if changing.borrow().conflict_boxes.contains(&(x_axis, y_axis)) {
+                CONFLICT_COLOR
+            } else {
+                ChangingPart::background(&binero.borrow().get(x_axis, y_axis), &binero.borrow().note(x_axis, y_axis))
+            };
+            changing.borrow_mut().grid.set_background(x_axis, y_axis, color);
+        }
+    }
+
+    /// Returns the explanation of a step of the resolution
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - a step of the resolution
+    fn explanation(step: &Step) -> String {
+        let value = step.value();
+        let other_value = value.the_other();
+        let line = match step.line() {
+            Some(Line::Row(i)) => tr!("row {}", i + 1),
+            Some(Line::Column(j)) => tr!("column {}", j + 1),
+            None => String::new(),
+        };
+        let reason = match step.technique() {
+            Technique::Pair => tr!("Two {}s are already side by side in the {}: three {}s would be adjacent.", other_value, line, other_value),
+            Technique::Sandwich => tr!("This box is between two {}s in the {}: three {}s would be adjacent.", other_value, line, other_value),
+            Technique::CountCompletion => tr!("The {} already contains as many {}s as possible.", line, other_value),
+            Technique::Uniqueness => tr!("A {} here would make the {} identical to another one.", other_value, line),
+            Technique::LookAhead => tr!("A {} here would lead to a contradiction.", other_value),
+        };
+        let mut result = tr!("This box must be a {} ({}).", value, step.technique().translate());
+        result.push('\n');
+        result.push_str(&reason);
+        result
+    }
+
+    const GRID_AREA: i32 = Grid::BOXES_PER_AREA * Grid::BOX_SIZE;
+    const HEIGHT: i32 = 50;
+    const DAILY_DIFFICULTY: Difficulty = Difficulty::Medium;
+    const SEED_HEIGHT: i32 = 30;
+    const SEED_SIZE: i32 = 12;
+    const STATUS_HEIGHT: i32 = 24;
+    const GENERATING_SIZE: i32 = 20;
+    const POLLING_DELAY: f64 = 0.1;
     const MARGIN_X: i32 = 20;
     const MARGIN_Y: i32 = 4;
 }
@@ -553,6 +1240,10 @@ enum PlayButton {
     Redo,
     Retry,
     Solve,
+    Hint,
+    Check,
+    Notes,
+    Cancel,
 }
 
 impl fmt::Display for PlayButton {
@@ -564,6 +1255,10 @@ impl fmt::Display for PlayButton {
             PlayButton::Redo => tr!("Redo"),
             PlayButton::Retry => tr!("Retry"),
             PlayButton::Solve => tr!("Solve"),
+            PlayButton::Hint => tr!("Hint"),
+            PlayButton::Check => tr!("Check"),
+            PlayButton::Notes => tr!("Notes"),
+            PlayButton::Cancel => tr!("Cancel"),
         };
         write!(f, "{}", printable)
     }
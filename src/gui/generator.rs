@@ -0,0 +1,106 @@
+//! # Generator
+//!
+//! `generator` generates the bineros on background threads and keeps a few of them ready
+
+use std::{collections::HashMap, sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}, mpsc::{self, Receiver}}, thread};
+use fltk::app::awake;
+use yabinero::engine::Binero;
+use yabinero::enums::{Difficulty, Size};
+
+/// The bineros generated in advance, for each size and each difficulty
+type Pool = HashMap<(Size, Difficulty), Vec<Binero>>;
+
+/// The generator of bineros
+pub struct Generator {
+    pool: Arc<Mutex<Pool>>,
+    refilling: Arc<Mutex<HashMap<(Size, Difficulty), Arc<AtomicBool>>>>,
+}
+
+impl Generator {
+    /// Returns a generator whose pool is empty
+    pub fn new() -> Generator {
+        Generator {
+            pool: Arc::new(Mutex::new(HashMap::new())),
+            refilling: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Returns a binero generated in advance, if there is one, and generates another one in the
+    /// background
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - a size
+    /// * `difficulty` - a difficulty
+    pub fn take(&self, size: Size, difficulty: Difficulty) -> Option<Binero> {
+        let result = self.pool.lock().unwrap().get_mut(&(size, difficulty)).and_then(|bineros| bineros.pop());
+        self.refill(size, difficulty);
+        result
+    }
+
+    /// Generates bineros in the background until the pool contains `Generator::POOL_SIZE` of
+    /// them for a size and a difficulty, stopping the generation for the other ones
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - a size
+    /// * `difficulty` - a difficulty
+    pub fn refill(&self, size: Size, difficulty: Difficulty) {
+        let key = (size, difficulty);
+        let cancelled = {
+            let mut refilling = self.refilling.lock().unwrap();
+            for (_, cancelled) in refilling.iter().filter(|&(&other_key, _)| other_key != key) {
+                cancelled.store(true, Ordering::Relaxed);
+            }
+            refilling.retain(|&other_key, _| other_key == key);
+            if refilling.contains_key(&key) {
+                return;
+            }
+            let cancelled = Arc::new(AtomicBool::new(false));
+            refilling.insert(key, Arc::clone(&cancelled));
+            cancelled
+        };
+        let cloned_pool = Arc::clone(&self.pool);
+        let cloned_refilling = Arc::clone(&self.refilling);
+        thread::spawn(move || {
+            loop {
+                {
+                    let pool = cloned_pool.lock().unwrap();
+                    if pool.get(&key).map_or(0, Vec::len) >= Generator::POOL_SIZE {
+                        let mut refilling = cloned_refilling.lock().unwrap();
+                        if refilling.get(&key).is_some_and(|flag| Arc::ptr_eq(flag, &cancelled)) {
+                            refilling.remove(&key);
+                        }
+                        break;
+                    }
+                }
+                match Binero::new_cancellable(size, difficulty, None, &cancelled) {
+                    Some(binero) => cloned_pool.lock().unwrap().entry(key).or_default().push(binero),
+                    None => break,
+                }
+            }
+        });
+    }
+
+    /// Generates a binero on a background thread and returns the `Receiver` that will get it,
+    /// the thread stopping without sending anything once `cancelled` is set
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - a size
+    /// * `difficulty` - a difficulty
+    /// * `seed` - the seed used to generate the binero or `None` to pick one randomly
+    /// * `cancelled` - a flag to set to stop the generation
+    pub fn generate(size: Size, difficulty: Difficulty, seed: Option<u64>, cancelled: Arc<AtomicBool>) -> Receiver<Binero> {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            if let Some(binero) = Binero::new_cancellable(size, difficulty, seed, &cancelled) && tx.send(binero).is_ok() {
+                awake();
+            }
+        });
+        rx
+    }
+
+    /// The number of bineros generated in advance for each size and each difficulty
+    const POOL_SIZE: usize = 2;
+}
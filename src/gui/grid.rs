@@ -0,0 +1,387 @@
+//! # Grid
+//!
+//! `grid` represents the grid of the GUI, which draws its boxes itself and passes what the player
+//! does in them to a handler
+
+use std::{cell::RefCell, rc::Rc};
+use fltk::{app::{self, MouseButton}, draw, enums::{Align, Color, Event, Font, FrameType, Key}, prelude::{WidgetBase, WidgetExt}, widget::Widget};
+use yabinero::enums::{Note, Size, Value};
+use crate::gui::{BG_COLOR, CURSOR_COLOR};
+
+/// What the player does in a box of the grid
+#[derive(Clone, Copy)]
+pub enum Entry {
+    /// A character typed in the box: `0`, `1` or `#`
+    Typed(char),
+    /// The box is emptied with Space, Backspace or Delete
+    Emptied,
+    /// A click on the box, with the right button when the values must be cycled backwards
+    Clicked { backwards: bool },
+}
+
+/// The handler of what the player does in a box, given by its x-axis and its y-axis
+type EntryHandler = Box<dyn FnMut(u8, u8, Entry)>;
+
+/// The grid of the GUI
+pub struct Grid {
+    widget: Widget,
+    state: Rc<RefCell<State>>,
+}
+
+/// What is displayed by the grid
+struct State {
+    size: Option<Size>,
+    boxes: Vec<Vec<GridBox>>,
+    cursor: (u8, u8),
+    color: Color,
+    ro_color: Color,
+    handler: Option<EntryHandler>,
+}
+
+/// What is displayed in a box of the grid
+#[derive(Clone, Copy)]
+struct GridBox {
+    value: Option<Value>,
+    note: Option<Note>,
+    given: bool,
+    background: Color,
+}
+
+impl Grid {
+    /// Returns an empty grid, hidden until a binero is displayed in it
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - the x-axis of the area of the grid
+    /// * `y` - the y-axis of the area of the grid
+    /// * `side` - the width and the height of the area of the grid
+    pub fn new(x: i32, y: i32, side: i32) -> Grid {
+        let mut widget = Widget::new(x, y, side, side, "");
+        let state = Rc::new(RefCell::new(State {
+            size: None,
+            boxes: Vec::new(),
+            cursor: (0, 0),
+            color: Color::Foreground,
+            ro_color: Color::Inactive,
+            handler: None,
+        }));
+        let cloned_state = Rc::clone(&state);
+        widget.draw(move |widget: &mut Widget| {
+            Grid::draw(widget, &cloned_state.borrow());
+        });
+        let cloned_state = Rc::clone(&state);
+        widget.handle(move |widget: &mut Widget, ev: Event| {
+            Grid::handle(widget, &cloned_state, ev)
+        });
+        widget.hide();
+        Grid {
+            widget,
+            state,
+        }
+    }
+
+    /// Empties the grid and gives it a size
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - a size
+    pub fn set_size(&mut self, size: Size) {
+        let empty_box = GridBox {
+            value: None,
+            note: None,
+            given: false,
+            background: Color::BackGround2,
+        };
+        {
+            let mut state = self.state.borrow_mut();
+            state.size = Some(size);
+            state.boxes = vec![vec![empty_box; size.width() as usize]; size.height() as usize];
+            state.cursor = (0, 0);
+        }
+        self.widget.redraw();
+    }
+
+    /// Sets the colors of the values
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - the color of the writable boxes
+    /// * `ro_color` - the color of the read-only boxes
+    pub fn set_colors(&mut self, color: Color, ro_color: Color) {
+        {
+            let mut state = self.state.borrow_mut();
+            state.color = color;
+            state.ro_color = ro_color;
+        }
+        self.widget.redraw();
+    }
+
+    /// Displays in a box its value or, if it has no value, its note
+    ///
+    /// # Arguments
+    ///
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `value` - a value
+    /// * `note` - a note
+    pub fn set_box(&mut self, x_axis: u8, y_axis: u8, value: Option<Value>, note: Option<Note>) {
+        {
+            let mut state = self.state.borrow_mut();
+            let a_box = &mut state.boxes[x_axis as usize][y_axis as usize];
+            a_box.value = value;
+            a_box.note = note;
+        }
+        self.widget.redraw();
+    }
+
+    /// Sets whether or not a box is given, so that the player cannot change it
+    ///
+    /// # Arguments
+    ///
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `given` - whether or not the box is given
+    pub fn set_given(&mut self, x_axis: u8, y_axis: u8, given: bool) {
+        self.state.borrow_mut().boxes[x_axis as usize][y_axis as usize].given = given;
+        self.widget.redraw();
+    }
+
+    /// Sets the background color of a box, used to highlight it
+    ///
+    /// # Arguments
+    ///
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `color` - a color
+    pub fn set_background(&mut self, x_axis: u8, y_axis: u8, color: Color) {
+        self.state.borrow_mut().boxes[x_axis as usize][y_axis as usize].background = color;
+        self.widget.redraw();
+    }
+
+    /// Moves the cursor to a box
+    ///
+    /// # Arguments
+    ///
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    pub fn move_cursor(&mut self, x_axis: u8, y_axis: u8) {
+        self.state.borrow_mut().cursor = (x_axis, y_axis);
+        self.widget.redraw();
+    }
+
+    /// Sets the handler of what the player does in the boxes that are not given
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - a closure receiving the x-axis and the y-axis of a box, and the entry
+    pub fn set_entry_handler<F: FnMut(u8, u8, Entry) + 'static>(&mut self, handler: F) {
+        self.state.borrow_mut().handler = Some(Box::new(handler));
+    }
+
+    /// Shows the grid and gives it the focus, so that the player can type at once
+    pub fn show(&mut self) {
+        self.widget.show();
+        app::set_focus(&self.widget);
+        self.widget.redraw();
+    }
+
+    /// Hides the grid
+    pub fn hide(&mut self) {
+        self.widget.hide();
+    }
+
+    /// Returns the side of the boxes, as large as possible in the area of the grid without being
+    /// larger than the boxes of the largest grids, and the position of the first box
+    ///
+    /// # Arguments
+    ///
+    /// * `widget` - the widget of the grid
+    /// * `size` - the size of the grid
+    fn layout(widget: &Widget, size: Size) -> (i32, i32, i32) {
+        let max_side = widget.w().min(widget.h()) / Grid::BOXES_PER_AREA;
+        let side = max_side.min(widget.w() / size.width() as i32).min(widget.h() / size.height() as i32);
+        let x = widget.x() + (widget.w() - size.width() as i32 * side) / 2;
+        let y = widget.y() + (widget.h() - size.height() as i32 * side) / 2;
+        (side, x, y)
+    }
+
+    /// Draws the boxes and, when the grid has the focus, the cursor
+    ///
+    /// # Arguments
+    ///
+    /// * `widget` - the widget of the grid
+    /// * `state` - what is displayed by the grid
+    fn draw(widget: &Widget, state: &State) {
+        draw::draw_rect_fill(widget.x(), widget.y(), widget.w(), widget.h(), BG_COLOR);
+        if let Some(size) = state.size {
+            let (side, start_x, start_y) = Grid::layout(widget, size);
+            let text_size = Grid::TEXT_SIZE * side / Grid::BOX_SIZE;
+            for (i, row) in state.boxes.iter().enumerate() {
+                for (j, a_box) in row.iter().enumerate() {
+                    let x = start_x + j as i32 * side;
+                    let y = start_y + i as i32 * side;
+                    draw::draw_box(FrameType::DownBox, x, y, side, side, a_box.background);
+                    draw::set_draw_color(if a_box.given { state.ro_color } else { state.color });
+                    match (a_box.value, a_box.note) {
+                        (Some(value), _) => {
+                            draw::set_font(Font::Helvetica, text_size);
+                            draw::draw_text2(&format!("{}", value), x, y, side, side, Align::Center);
+                        },
+                        (None, Some(Note::Probably(value))) => {
+                            draw::set_font(Font::HelveticaItalic, text_size * 2 / 3);
+                            draw::draw_text2(&format!("{}?", value), x, y, side, side, Align::Center);
+                        },
+                        _ => (),
+                    }
+                }
+            }
+            if widget.has_focus() {
+                let (i, j) = state.cursor;
+                draw::set_line_style(draw::LineStyle::Solid, Grid::CURSOR_WIDTH);
+                draw::draw_rect_with_color(start_x + j as i32 * side + 1, start_y + i as i32 * side + 1, side - 2, side - 2, CURSOR_COLOR);
+                draw::set_line_style(draw::LineStyle::Solid, 0);
+            }
+        }
+    }
+
+    /// Handles the events of the grid: the keys move the cursor or write in the box under it,
+    /// the clicks move the cursor and cycle the value of the clicked box
+    ///
+    /// # Arguments
+    ///
+    /// * `widget` - the widget of the grid
+    /// * `state` - what is displayed by the grid
+    /// * `ev` - an event
+    fn handle(widget: &mut Widget, state: &Rc<RefCell<State>>, ev: Event) -> bool {
+        let size = match state.borrow().size {
+            Some(size) => size,
+            None => return false,
+        };
+        match ev {
+            Event::Focus | Event::Unfocus => {
+                widget.redraw();
+                true
+            },
+            Event::Push => {
+                let button = app::event_mouse_button();
+                if button != MouseButton::Left && button != MouseButton::Right {
+                    return false;
+                }
+                match Grid::box_at(widget, size, app::event_x(), app::event_y()) {
+                    Some((x_axis, y_axis)) => {
+                        app::set_focus(&*widget);
+                        state.borrow_mut().cursor = (x_axis, y_axis);
+                        widget.redraw();
+                        Grid::enter(state, x_axis, y_axis, Entry::Clicked { backwards: button == MouseButton::Right });
+                        true
+                    },
+                    None => false,
+                }
+            },
+            Event::KeyDown => {
+                let (x_axis, y_axis) = state.borrow().cursor;
+                if let Some(cursor) = Grid::neighbour(size, x_axis, y_axis, app::event_key(), app::is_event_ctrl()) {
+                    state.borrow_mut().cursor = cursor;
+                    widget.redraw();
+                    true
+                } else if let Some(entry) = Grid::typed_entry(app::event_key(), &app::event_text()) {
+                    Grid::enter(state, x_axis, y_axis, entry);
+                    true
+                } else {
+                    false
+                }
+            },
+            _ => false,
+        }
+    }
+
+    /// Passes to the handler what the player does in a box, unless the box is given
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - what is displayed by the grid
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `entry` - what the player does
+    fn enter(state: &Rc<RefCell<State>>, x_axis: u8, y_axis: u8, entry: Entry) {
+        if state.borrow().boxes[x_axis as usize][y_axis as usize].given {
+            return;
+        }
+        let handler = state.borrow_mut().handler.take();
+        if let Some(mut handler) = handler {
+            handler(x_axis, y_axis, entry);
+            let mut state = state.borrow_mut();
+            if state.handler.is_none() {
+                state.handler = Some(handler);
+            }
+        }
+    }
+
+    /// Returns the box under a point, or `None` if there is no box there
+    ///
+    /// # Arguments
+    ///
+    /// * `widget` - the widget of the grid
+    /// * `size` - the size of the grid
+    /// * `x` - the x-axis of the point
+    /// * `y` - the y-axis of the point
+    fn box_at(widget: &Widget, size: Size, x: i32, y: i32) -> Option<(u8, u8)> {
+        let (side, start_x, start_y) = Grid::layout(widget, size);
+        if x < start_x || y < start_y {
+            return None;
+        }
+        let (x_axis, y_axis) = ((y - start_y) / side, (x - start_x) / side);
+        if x_axis < size.height() as i32 && y_axis < size.width() as i32 {
+            Some((x_axis as u8, y_axis as u8))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the box reached from a box with a navigation key, or `None` if the key is not a
+    /// navigation one: the arrows move to the next box, Home and End to the first and the last
+    /// boxes of the row, or of the grid with Ctrl
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the grid
+    /// * `x_axis` - an unsigned 8-bit integer that gives the x-axis
+    /// * `y_axis` - an unsigned 8-bit integer that gives the y-axis
+    /// * `key` - the pressed key
+    /// * `ctrl` - whether or not Ctrl is pressed
+    fn neighbour(size: Size, x_axis: u8, y_axis: u8, key: Key, ctrl: bool) -> Option<(u8, u8)> {
+        let (last_x, last_y) = (size.height() - 1, size.width() - 1);
+        match key {
+            Key::Up => Some((x_axis.saturating_sub(1), y_axis)),
+            Key::Down => Some(((x_axis + 1).min(last_x), y_axis)),
+            Key::Left => Some((x_axis, y_axis.saturating_sub(1))),
+            Key::Right => Some((x_axis, (y_axis + 1).min(last_y))),
+            Key::Home if ctrl => Some((0, 0)),
+            Key::Home => Some((x_axis, 0)),
+            Key::End if ctrl => Some((last_x, last_y)),
+            Key::End => Some((x_axis, last_y)),
+            _ => None,
+        }
+    }
+
+    /// Returns what the player does in a box by pressing a key, or `None` if the key does not
+    /// write in a box: `0`, `1` and `#` are typed, Space, Backspace and Delete empty the box
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the pressed key
+    /// * `text` - the text of the pressed key
+    fn typed_entry(key: Key, text: &str) -> Option<Entry> {
+        match text {
+            "0" | "1" | "#" => text.chars().next().map(Entry::Typed),
+            " " => Some(Entry::Emptied),
+            _ if key == Key::BackSpace || key == Key::Delete => Some(Entry::Emptied),
+            _ => None,
+        }
+    }
+
+    pub const BOX_SIZE: i32 = 32;
+    pub const BOXES_PER_AREA: i32 = 16;
+    const TEXT_SIZE: i32 = 20;
+    const CURSOR_WIDTH: i32 = 3;
+}
@@ -2,13 +2,15 @@
 //!
 //! `menu` contains the functions that handles the menu
 
-use std::{cell::RefCell, fmt, fs, path::Path, rc::Rc, sync::mpsc::Sender};
-use fltk::{app::{App, AppScheme, quit}, button::Button, enums::Shortcut, group::ColorChooser, prelude::{MenuExt, WidgetBase, WidgetExt}, menu::{MenuBar, MenuFlag}};
+use std::{cell::RefCell, env, fmt, fs, path::{Path, PathBuf}, rc::Rc};
+use fltk::{app::{App, AppScheme, quit}, browser::HoldBrowser, button::Button, dialog::{choice2_default, file_chooser, input_default, NativeFileChooser, NativeFileChooserOptions, NativeFileChooserType}, enums::Shortcut, group::ColorChooser, prelude::{BrowserExt, MenuExt, WidgetBase, WidgetExt}, menu::{MenuBar, MenuFlag}};
 use tr::tr;
 use enum_iterator::all;
 use regex::Regex;
-use crate::enums::{Difficulty, Size};
-use crate::gui::{BG_COLOR, SELECT_COLOR, BUTTON_HEIGHT, display_window, show, popup_window, user_data::{UserPrefs, BestScores}, changing::ChangingPart};
+use yabinero::engine::{Binero, pack::Pack};
+use yabinero::enums::{Difficulty, Size};
+use crate::translation::Translate;
+use crate::gui::{BG_COLOR, SELECT_COLOR, BUTTON_HEIGHT, display_alert, display_window, show, popup_window, user_data::{UserPrefs, BestScores, DailyScores, PackScores}, changing::ChangingPart};
 
 /// Returns an empty menu bar
 ///
@@ -43,21 +45,25 @@ pub fn add_entries(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>, chan
 /// * `menu` - a menu bar
 /// * `user_prefs` - the user's preferences
 pub fn set_menu_items(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>) {
-    let size = format!("{}", user_prefs.borrow().size());
+    let size = user_prefs.borrow().size();
+    let size = if Size::squares().contains(&size) {
+        format!("{}", size)
+    } else {
+        tr!("Custom…")
+    };
     let size = entry_label(&TopLevelMenu::Options, &Submenu::Size, Some(&size));
     if let Some(mut menu_item) = menu.find_item(&size) {
         menu_item.set();
     }
-    let difficulty = format!("{}", user_prefs.borrow().difficulty());
+    let difficulty = user_prefs.borrow().difficulty().translate();
     let difficulty = entry_label(&TopLevelMenu::Options, &Submenu::Difficulty, Some(&difficulty));
     if let Some(mut menu_item) = menu.find_item(&difficulty) {
         menu_item.set();
     }
-    if user_prefs.borrow().sounds() {
-        if let Some(mut menu_item) = menu.find_item(&entry_label(&TopLevelMenu::Options, &Submenu::Sounds, None)) {
-            menu_item.set();
-        }
-    }
+    set_toggle_item(menu, &Submenu::Sounds, user_prefs.borrow().sounds());
+    set_toggle_item(menu, &Submenu::CheckImmediately, user_prefs.borrow().check_immediately());
+    set_toggle_item(menu, &Submenu::AutoAdvance, user_prefs.borrow().auto_advance());
+    set_toggle_item(menu, &Submenu::HighlightConflicts, user_prefs.borrow().highlight_conflicts());
     let theme = format!("{:?}", user_prefs.borrow().theme());
     let theme = entry_label(&TopLevelMenu::Options, &Submenu::Theme, Some(&theme));
     if let Some(mut menu_item) = menu.find_item(&theme) {
@@ -65,6 +71,22 @@ pub fn set_menu_items(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>) {
     }
 }
 
+/// Sets a toggle entry of the "Options" menu if the corresponding option is activated
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `submenu` - a submenu entry
+/// * `activated` - whether or not the option is activated
+fn set_toggle_item(menu: &mut MenuBar, submenu: &Submenu, activated: bool) {
+    if !activated {
+        return;
+    }
+    if let Some(mut menu_item) = menu.find_item(&entry_label(&TopLevelMenu::Options, submenu, None)) {
+        menu_item.set();
+    }
+}
+
 /// Adds the entries to the "Game" menu
 ///
 /// # Arguments
@@ -74,10 +96,21 @@ pub fn set_menu_items(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>) {
 /// * `changing` - the changing part of the GUI
 fn add_game_entries(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>) {
     add_new_game(menu, user_prefs, changing);
+    add_new_game_from_seed(menu, user_prefs, changing);
+    add_daily_puzzle(menu, user_prefs, changing);
+    add_puzzle_packs(menu, user_prefs, changing);
+    add_open_puzzle(menu, user_prefs, changing);
+    add_save_puzzle(menu, changing);
     add_undo(menu, changing);
     add_redo(menu, changing);
+    add_hint(menu, user_prefs, changing);
+    add_check(menu, user_prefs, changing);
+    add_notes(menu, changing);
+    add_set_checkpoint(menu, changing);
+    add_history(menu, changing);
     add_best_scores(menu, user_prefs);
-    add_quit(menu);
+    add_daily_scores(menu, user_prefs);
+    add_quit(menu, user_prefs, changing);
 }
 
 /// Adds the entries to the "Options" menu
@@ -91,6 +124,9 @@ fn add_options_entries(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>,
     add_sizes(menu, user_prefs);
     add_difficulties(menu, user_prefs);
     add_sounds(menu, user_prefs);
+    add_check_immediately(menu, user_prefs);
+    add_auto_advance(menu, user_prefs);
+    add_highlight_conflicts(menu, user_prefs);
     add_themes(menu, user_prefs, app);
     add_colors(menu, user_prefs);
 }
@@ -115,16 +151,142 @@ fn add_help_entries(menu: &mut MenuBar) {
 fn add_new_game(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>) {
     let cloned_prefs = Rc::clone(user_prefs);
     let cloned_changing = Rc::clone(changing);
-    let mut tx: Option<Sender<bool>> = None;
     menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::New, None), Shortcut::Ctrl | 'n', MenuFlag::Normal, Box::new(move |_: &mut MenuBar| {
-        if let Some(t) = &tx {
-            t.send(true).unwrap();
-            ChangingPart::pause_game(&cloned_changing);
+        ChangingPart::new_game(&cloned_prefs, &cloned_changing, None);
+    }));
+}
+
+/// Adds the "Game/New from seed" menu entry
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `user_prefs` - the user's preferences
+/// * `changing` - the changing part of the GUI
+fn add_new_game_from_seed(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>) {
+    let cloned_prefs = Rc::clone(user_prefs);
+    let cloned_changing = Rc::clone(changing);
+    menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::NewFromSeed, None), Shortcut::Ctrl | Shortcut::Shift | 'n', MenuFlag::Normal, Box::new(move |_: &mut MenuBar| {
+        if let Some(input) = input_default(&tr!("Seed:"), "") {
+            match input.trim().parse() {
+                Ok(seed) => ChangingPart::new_game(&cloned_prefs, &cloned_changing, Some(seed)),
+                Err(_) => display_alert(&tr!("This seed is not valid!")),
+            }
+        }
+    }));
+}
+
+/// Adds the "Game/Daily puzzle" menu entry
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `user_prefs` - the user's preferences
+/// * `changing` - the changing part of the GUI
+fn add_daily_puzzle(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>) {
+    let cloned_prefs = Rc::clone(user_prefs);
+    let cloned_changing = Rc::clone(changing);
+    menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::DailyPuzzle, None), Shortcut::Ctrl | 'd', MenuFlag::Normal, Box::new(move |_: &mut MenuBar| {
+        ChangingPart::new_daily_game(&cloned_prefs, &cloned_changing);
+    }));
+}
+
+/// Adds the "Game/Puzzle packs…" menu entry
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `user_prefs` - the user's preferences
+/// * `changing` - the changing part of the GUI
+fn add_puzzle_packs(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>) {
+    let cloned_prefs = Rc::clone(user_prefs);
+    let cloned_changing = Rc::clone(changing);
+    menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::PuzzlePacks, None), Shortcut::Ctrl | 'p', MenuFlag::Normal, Box::new(move |_: &mut MenuBar| {
+        display_packs(&cloned_prefs, &cloned_changing);
+    }));
+}
+
+/// Adds the "Game/Open puzzle…" menu entry
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `user_prefs` - the user's preferences
+/// * `changing` - the changing part of the GUI
+fn add_open_puzzle(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>) {
+    let cloned_prefs = Rc::clone(user_prefs);
+    let cloned_changing = Rc::clone(changing);
+    menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::OpenPuzzle, None), Shortcut::Ctrl | 'o', MenuFlag::Normal, Box::new(move |_: &mut MenuBar| {
+        if let Some(path) = file_chooser(&tr!("Open puzzle"), "*.{txt,line}", ".", false) {
+            match fs::read_to_string(&path) {
+                Ok(text) => match text.parse::<Binero>() {
+                    Ok(binero) => ChangingPart::start_game(&cloned_prefs, &cloned_changing, binero, 0, 0, None, None),
+                    Err(error) => display_alert(&error.translate()),
+                },
+                Err(_) => display_alert(&tr!("The file cannot be read!")),
+            }
         }
-        tx = Some(ChangingPart::new_game(&cloned_prefs, &cloned_changing));
     }));
 }
 
+/// Adds the "Game/Save puzzle as…" menu entry
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `changing` - the changing part of the GUI
+fn add_save_puzzle(menu: &mut MenuBar, changing: &Rc<RefCell<ChangingPart>>) {
+    let cloned_changing = Rc::clone(changing);
+    menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::SavePuzzle, None), Shortcut::Ctrl | 's', MenuFlag::Normal, Box::new(move |_: &mut MenuBar| {
+        match ChangingPart::binero(&cloned_changing) {
+            Some(binero) => save_puzzle(&binero.borrow()),
+            None => display_alert(&tr!("There is no puzzle to save!")),
+        }
+    }));
+}
+
+/// Saves a binero in a file chosen by the user, as text grids, a JSON document or a single line
+/// depending on the extension of the file, which is added from the chosen file type when it is
+/// missing
+///
+/// # Arguments
+///
+/// * `binero` - a binero
+fn save_puzzle(binero: &Binero) {
+    let mut chooser = NativeFileChooser::new(NativeFileChooserType::BrowseSaveFile);
+    chooser.set_title(&tr!("Save puzzle as"));
+    chooser.set_option(NativeFileChooserOptions::SaveAsConfirm);
+    chooser.set_filter(&format!("{}\t*.{}\n{}\t*.{}\n{}\t*.{}", tr!("Text grid"), PUZZLE_EXTENSIONS[0], tr!("JSON document"), PUZZLE_EXTENSIONS[1], tr!("Single line"), PUZZLE_EXTENSIONS[2]));
+    chooser.show();
+    let mut path = chooser.filename();
+    if path.as_os_str().is_empty() {
+        return;
+    }
+    let extension = path.extension().and_then(|extension| extension.to_str()).map(str::to_lowercase);
+    let extension = match extension {
+        Some(extension) if PUZZLE_EXTENSIONS.contains(&extension.as_str()) => extension,
+        _ => {
+            let extension = PUZZLE_EXTENSIONS.get(chooser.filter_value() as usize).unwrap_or(&PUZZLE_EXTENSIONS[0]);
+            let mut name = path.into_os_string();
+            name.push(".");
+            name.push(extension);
+            path = PathBuf::from(name);
+            if path.exists() && choice2_default(&tr!("{} already exists. Do you want to replace it?", path.display()), &tr!("No"), &tr!("Yes"), "") != Some(1) {
+                return;
+            }
+            extension.to_string()
+        },
+    };
+    let content = match extension.as_str() {
+        "json" => binero.to_json(),
+        "line" => format!("{}\n", binero.to_compact_string()),
+        _ => binero.to_text(),
+    };
+    if fs::write(&path, content).is_err() {
+        display_alert(&tr!("The puzzle cannot be saved!"));
+    }
+}
+
 /// Adds the "Game/Undo" menu entry
 ///
 /// # Arguments
@@ -151,6 +313,81 @@ fn add_redo(menu: &mut MenuBar, changing: &Rc<RefCell<ChangingPart>>) {
     }));
 }
 
+/// Adds the "Game/Hint" menu entry
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `user_prefs` - the user's preferences
+/// * `changing` - the changing part of the GUI
+fn add_hint(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>) {
+    let cloned_prefs = Rc::clone(user_prefs);
+    let cloned_changing = Rc::clone(changing);
+    menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::Hint, None), Shortcut::Ctrl | 'i', MenuFlag::Normal, Box::new(move |_: &mut MenuBar| {
+        ChangingPart::hint(&cloned_changing, &cloned_prefs);
+    }));
+}
+
+/// Adds the "Game/Check" menu entry
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `user_prefs` - the user's preferences
+/// * `changing` - the changing part of the GUI
+fn add_check(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>) {
+    let cloned_prefs = Rc::clone(user_prefs);
+    let cloned_changing = Rc::clone(changing);
+    menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::Check, None), Shortcut::Ctrl | 'k', MenuFlag::Normal, Box::new(move |_: &mut MenuBar| {
+        ChangingPart::check(&cloned_changing, &cloned_prefs);
+    }));
+}
+
+/// Adds the "Game/Notes" menu entry
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `changing` - the changing part of the GUI
+fn add_notes(menu: &mut MenuBar, changing: &Rc<RefCell<ChangingPart>>) {
+    let cloned_changing = Rc::clone(changing);
+    menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::Notes, None), Shortcut::Ctrl | 'm', MenuFlag::Normal, Box::new(move |_: &mut MenuBar| {
+        ChangingPart::toggle_notes(&cloned_changing);
+    }));
+}
+
+/// Adds the "Game/Set checkpoint…" menu entry
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `changing` - the changing part of the GUI
+fn add_set_checkpoint(menu: &mut MenuBar, changing: &Rc<RefCell<ChangingPart>>) {
+    let cloned_changing = Rc::clone(changing);
+    menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::SetCheckpoint, None), Shortcut::Ctrl | 'b', MenuFlag::Normal, Box::new(move |_: &mut MenuBar| {
+        let Some(binero) = ChangingPart::binero(&cloned_changing) else {
+            return;
+        };
+        let default_name = tr!("Checkpoint {}", binero.borrow().history().checkpoints().len() + 1);
+        if let Some(name) = input_default(&tr!("Name of the checkpoint:"), &default_name) {
+            ChangingPart::add_checkpoint(&cloned_changing, &name);
+        }
+    }));
+}
+
+/// Adds the "Game/History…" menu entry
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `changing` - the changing part of the GUI
+fn add_history(menu: &mut MenuBar, changing: &Rc<RefCell<ChangingPart>>) {
+    let cloned_changing = Rc::clone(changing);
+    menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::History, None), Shortcut::Ctrl | 'y', MenuFlag::Normal, Box::new(move |_: &mut MenuBar| {
+        display_history(&cloned_changing);
+    }));
+}
+
 /// Adds the "Game/Best scores" menu entry
 ///
 /// # Arguments
@@ -161,7 +398,21 @@ fn add_best_scores(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>) {
     let cloned_prefs = Rc::clone(user_prefs);
     menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::BestScores, None), Shortcut::None, MenuFlag::Normal, Box::new(move |_: &mut MenuBar| {
         let best_scores = BestScores::new().best_scores(cloned_prefs.borrow().size(), cloned_prefs.borrow().difficulty());
-        display_window(326, 230, &tr!("Best scores"), &best_scores, true, 184, None);
+        display_window(440, 230, &tr!("Best scores"), &best_scores, true, 184, None);
+    }));
+}
+
+/// Adds the "Game/Daily scores" menu entry
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `user_prefs` - the user's preferences
+fn add_daily_scores(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>) {
+    let cloned_prefs = Rc::clone(user_prefs);
+    menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::DailyScores, None), Shortcut::None, MenuFlag::Normal, Box::new(move |_: &mut MenuBar| {
+        let daily_scores = DailyScores::new().daily_scores(cloned_prefs.borrow().size());
+        display_window(440, 230, &tr!("Daily scores"), &daily_scores, true, 184, None);
     }));
 }
 
@@ -170,8 +421,16 @@ fn add_best_scores(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>) {
 /// # Arguments
 ///
 /// * `menu` - a menu bar
-fn add_quit(menu: &mut MenuBar) {
-    menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::Quit, None), Shortcut::Ctrl | 'q', MenuFlag::Normal, Box::new(|_: &mut MenuBar| {
+/// * `user_prefs` - the user's preferences
+/// * `changing` - the changing part of the GUI
+fn add_quit(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>) {
+    let cloned_prefs = Rc::clone(user_prefs);
+    let cloned_changing = Rc::clone(changing);
+    menu.add(&entry_label(&TopLevelMenu::Game, &Submenu::Quit, None), Shortcut::Ctrl | 'q', MenuFlag::Normal, Box::new(move |menu: &mut MenuBar| {
+        ChangingPart::save_game(&cloned_changing);
+        if let Some(window) = menu.window() {
+            cloned_prefs.borrow_mut().set_geometry(window.x(), window.y(), window.w(), window.h());
+        }
         quit();
     }));
 }
@@ -183,12 +442,23 @@ fn add_quit(menu: &mut MenuBar) {
 /// * `menu` - a menu bar
 /// * `user_prefs` - the user's preferences
 fn add_sizes(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>) {
-    for size in all::<Size>() {
+    for size in Size::squares() {
         let cloned_prefs = Rc::clone(user_prefs);
         menu.add(&entry_label(&TopLevelMenu::Options, &Submenu::Size, Some(&format!("{}", size))), Shortcut::None, MenuFlag::Radio, Box::new(move |_: &mut MenuBar| {
             cloned_prefs.borrow_mut().set_size(size);
         }));
     }
+    let cloned_prefs = Rc::clone(user_prefs);
+    menu.add(&entry_label(&TopLevelMenu::Options, &Submenu::Size, Some(&tr!("Custom…"))), Shortcut::None, MenuFlag::Radio, Box::new(move |menu: &mut MenuBar| {
+        let current = format!("{}", cloned_prefs.borrow().size());
+        if let Some(input) = input_default(&tr!("Width x height:"), &current) {
+            match input.parse::<Size>() {
+                Ok(size) => cloned_prefs.borrow_mut().set_size(size),
+                Err(_) => display_alert(&tr!("The width and the height must be between {} and {}, neither more than twice the other!", Size::MIN_SIDE, Size::MAX_SIDE)),
+            }
+        }
+        set_menu_items(menu, &cloned_prefs);
+    }));
 }
 
 /// Adds the "Options/Difficulty/..." menu entry
@@ -200,7 +470,7 @@ fn add_sizes(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>) {
 fn add_difficulties(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>) {
     for difficulty in all::<Difficulty>() {
         let cloned_prefs = Rc::clone(user_prefs);
-        menu.add(&entry_label(&TopLevelMenu::Options, &Submenu::Difficulty, Some(&format!("{}", difficulty))), Shortcut::None, MenuFlag::Radio, Box::new(move |_: &mut MenuBar| {
+        menu.add(&entry_label(&TopLevelMenu::Options, &Submenu::Difficulty, Some(&difficulty.translate())), Shortcut::None, MenuFlag::Radio, Box::new(move |_: &mut MenuBar| {
             cloned_prefs.borrow_mut().set_difficulty(difficulty);
         }));
     }
@@ -220,6 +490,48 @@ fn add_sounds(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>) {
     }));
 }
 
+/// Adds the "Options/Check immediately" menu entry
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `user_prefs` - the user's preferences
+fn add_check_immediately(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>) {
+    let cloned_prefs = Rc::clone(user_prefs);
+    menu.add(&entry_label(&TopLevelMenu::Options, &Submenu::CheckImmediately, None), Shortcut::None, MenuFlag::Toggle, Box::new(move |_: &mut MenuBar| {
+        let old_value = cloned_prefs.borrow().check_immediately();
+        cloned_prefs.borrow_mut().set_check_immediately(!old_value);
+    }));
+}
+
+/// Adds the "Options/Auto-advance" menu entry
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `user_prefs` - the user's preferences
+fn add_auto_advance(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>) {
+    let cloned_prefs = Rc::clone(user_prefs);
+    menu.add(&entry_label(&TopLevelMenu::Options, &Submenu::AutoAdvance, None), Shortcut::None, MenuFlag::Toggle, Box::new(move |_: &mut MenuBar| {
+        let old_value = cloned_prefs.borrow().auto_advance();
+        cloned_prefs.borrow_mut().set_auto_advance(!old_value);
+    }));
+}
+
+/// Adds the "Options/Highlight conflicts" menu entry
+///
+/// # Arguments
+///
+/// * `menu` - a menu bar
+/// * `user_prefs` - the user's preferences
+fn add_highlight_conflicts(menu: &mut MenuBar, user_prefs: &Rc<RefCell<UserPrefs>>) {
+    let cloned_prefs = Rc::clone(user_prefs);
+    menu.add(&entry_label(&TopLevelMenu::Options, &Submenu::HighlightConflicts, None), Shortcut::None, MenuFlag::Toggle, Box::new(move |_: &mut MenuBar| {
+        let old_value = cloned_prefs.borrow().highlight_conflicts();
+        cloned_prefs.borrow_mut().set_highlight_conflicts(!old_value);
+    }));
+}
+
 /// Adds the "Options/Theme/..." menu entry
 ///
 /// # Arguments
@@ -322,6 +634,178 @@ fn display_color_chooser(user_prefs: &Rc<RefCell<UserPrefs>>, read_only: bool) {
     }));
 }
 
+/// Returns the packs directory: the one next to the executable of the game if there is one, so
+/// that the game can be launched from anywhere, or else the one of the current directory
+fn packs_dir() -> PathBuf {
+    env::current_exe().ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(PACKS_DIR)))
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(|| PathBuf::from(PACKS_DIR))
+}
+
+/// Returns the packs found in the packs directory, sorted by file name
+fn load_packs() -> Vec<Pack> {
+    let mut paths: Vec<PathBuf> = match fs::read_dir(packs_dir()) {
+        Ok(entries) => entries.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    paths.sort();
+    let mut result = Vec::new();
+    for path in paths {
+        match fs::read_to_string(&path) {
+            Ok(text) => match text.parse::<Pack>() {
+                Ok(pack) => result.push(pack),
+                Err(error) => display_alert(&format!("{}\n{}", path.display(), error.translate())),
+            },
+            Err(_) => display_alert(&tr!("The file cannot be read!")),
+        }
+    }
+    result
+}
+
+/// Fills a browser with the bineros of a pack, their size, their difficulty and their best score
+///
+/// # Arguments
+///
+/// * `browser` - a browser
+/// * `pack` - a pack
+fn fill_pack_puzzles(browser: &mut HoldBrowser, pack: &Pack) {
+    let scores = PackScores::new();
+    browser.clear();
+    for puzzle in pack.puzzles() {
+        let score = scores.pack_score(pack.name(), puzzle.id()).map_or("-".to_owned(), |score| score.replace("\t\t", "\t"));
+        browser.add(&format!("{}\t{}\t{}\t{}", puzzle.id(), puzzle.size(), puzzle.difficulty().translate(), score));
+    }
+}
+
+/// Displays the packs and their bineros, so that the player can choose one
+///
+/// # Arguments
+///
+/// * `user_prefs` - the user's preferences
+/// * `changing` - the changing part of the GUI
+fn display_packs(user_prefs: &Rc<RefCell<UserPrefs>>, changing: &Rc<RefCell<ChangingPart>>) {
+    let packs = load_packs();
+    if packs.is_empty() {
+        display_alert(&tr!("No puzzle pack was found!"));
+        return;
+    }
+    let width = 700;
+    let browser_height = 300;
+    let pack_width = 200;
+    let mut window = popup_window(width, browser_height + BUTTON_HEIGHT + 20, &tr!("Puzzle packs"));
+    let mut pack_browser = HoldBrowser::new(5, 5, pack_width, browser_height, "");
+    pack_browser.set_column_char('\t');
+    pack_browser.set_column_widths(&[150]);
+    let mut puzzle_browser = HoldBrowser::new(pack_width + 10, 5, width - pack_width - 15, browser_height, "");
+    puzzle_browser.set_column_char('\t');
+    puzzle_browser.set_column_widths(&[50, 70, 90, 60, 150]);
+    let scores = PackScores::new();
+    for pack in &packs {
+        pack_browser.add(&format!("{}\t{}/{}", pack.name(), scores.solved(pack), pack.puzzles().len()));
+    }
+    pack_browser.select(1);
+    fill_pack_puzzles(&mut puzzle_browser, &packs[0]);
+    let mut but_close = button(width / 2 - BUTTON_WIDTH - 5, browser_height + 12, &tr!("Close"));
+    let mut but_play = button(width / 2 + 5, browser_height + 12, &tr!("Play"));
+    show(&mut window);
+    let packs = Rc::new(packs);
+    let cloned_packs = Rc::clone(&packs);
+    let mut cloned_puzzle_browser = puzzle_browser.clone();
+    pack_browser.set_callback(Box::new(move |browser: &mut HoldBrowser| {
+        if browser.value() > 0 {
+            fill_pack_puzzles(&mut cloned_puzzle_browser, &cloned_packs[browser.value() as usize - 1]);
+        }
+    }));
+    let window = Rc::new(RefCell::new(window));
+    let window2 = Rc::clone(&window);
+    but_close.set_callback(Box::new(move |_: &mut Button| {
+        window.borrow_mut().hide();
+    }));
+    let cloned_prefs = Rc::clone(user_prefs);
+    let cloned_changing = Rc::clone(changing);
+    but_play.set_callback(Box::new(move |_: &mut Button| {
+        if pack_browser.value() == 0 || puzzle_browser.value() == 0 {
+            display_alert(&tr!("Choose a puzzle first!"));
+            return;
+        }
+        let pack = &packs[pack_browser.value() as usize - 1];
+        let puzzle = &pack.puzzles()[puzzle_browser.value() as usize - 1];
+        window2.borrow_mut().hide();
+        ChangingPart::start_game(&cloned_prefs, &cloned_changing, puzzle.binero(), 0, 0, None, Some(PackScores::key(pack.name(), puzzle.id())));
+    }));
+}
+
+/// Fills a browser with the actions of the history of a binero, indented at each branching, with
+/// their checkpoints and the current action
+///
+/// # Arguments
+///
+/// * `browser` - a browser
+/// * `binero` - a binero
+fn fill_history(browser: &mut HoldBrowser, binero: &Binero) {
+    let as_str = |value: Option<String>| value.unwrap_or_else(|| "·".to_owned());
+    let history = binero.history();
+    browser.clear();
+    for (i, depth) in history.branches() {
+        let item = &history.items()[i];
+        let change = if item.old_value() != item.new_value() {
+            format!("{} → {}", as_str(item.old_value().map(|value| format!("{}", value))), as_str(item.new_value().map(|value| format!("{}", value))))
+        } else {
+            tr!("note {} → {}", as_str(item.old_note().map(|note| format!("{}", note))), as_str(item.new_note().map(|note| format!("{}", note))))
+        };
+        let mut line = format!("{}{}, {}: {}", "    ".repeat(depth), tr!("row {}", item.x_axis() + 1), tr!("column {}", item.y_axis() + 1), change);
+        for checkpoint in history.checkpoints().iter().filter(|checkpoint| checkpoint.item() == Some(i)) {
+            line.push_str(&format!(" [{}]", checkpoint.name()));
+        }
+        if history.current_item() == Some(i) {
+            line.push_str(" ◀");
+        }
+        browser.add(&line);
+    }
+}
+
+/// Displays the checkpoints and the branches of the history of the current game, so that the
+/// player can go back to a checkpoint
+///
+/// # Arguments
+///
+/// * `changing` - the changing part of the GUI
+fn display_history(changing: &Rc<RefCell<ChangingPart>>) {
+    let Some(binero) = ChangingPart::binero(changing) else {
+        return;
+    };
+    let width = 700;
+    let browser_height = 300;
+    let checkpoint_width = 200;
+    let mut window = popup_window(width, browser_height + BUTTON_HEIGHT + 20, &tr!("History"));
+    let mut checkpoint_browser = HoldBrowser::new(5, 5, checkpoint_width, browser_height, "");
+    let mut history_browser = HoldBrowser::new(checkpoint_width + 10, 5, width - checkpoint_width - 15, browser_height, "");
+    for checkpoint in binero.borrow().history().checkpoints() {
+        checkpoint_browser.add(checkpoint.name());
+    }
+    fill_history(&mut history_browser, &binero.borrow());
+    let mut but_close = button(width / 2 - BUTTON_WIDTH - 5, browser_height + 12, &tr!("Close"));
+    let mut but_roll_back = button(width / 2 + 5, browser_height + 12, &tr!("Go back"));
+    show(&mut window);
+    let window = Rc::new(RefCell::new(window));
+    let window2 = Rc::clone(&window);
+    but_close.set_callback(Box::new(move |_: &mut Button| {
+        window.borrow_mut().hide();
+    }));
+    let cloned_changing = Rc::clone(changing);
+    but_roll_back.set_callback(Box::new(move |_: &mut Button| {
+        if checkpoint_browser.value() == 0 {
+            display_alert(&tr!("Choose a checkpoint first!"));
+            return;
+        }
+        window2.borrow_mut().hide();
+        ChangingPart::roll_back_to(&cloned_changing, checkpoint_browser.value() as usize - 1);
+    }));
+}
+
 /// Returns the help of the game
 fn about() -> String {
     let authors = authors();
@@ -332,7 +816,7 @@ fn about() -> String {
     result.push_str(&new_line(authors_number));
     result.push_str(&tr!("The aim of the game is to fill in a grid with 0 and 1 respecting"));
     result.push_str("\n");
-    result.push_str(&tr!("two constraints:"));
+    result.push_str(&tr!("three constraints:"));
     result.push_str("\n\n\t- ");
     result.push_str(&tr!("In each line or column, there must be the same number"));
     result.push_str("\n\t  ");
@@ -341,6 +825,8 @@ fn about() -> String {
     result.push_str(&tr!("In each line or column, the same value cannot be side by"));
     result.push_str("\n\t  ");
     result.push_str(&tr!("side more than twice."));
+    result.push_str("\n\n\t- ");
+    result.push_str(&tr!("Two lines or two columns cannot be identical."));
     result.push_str(&new_line(authors_number));
     result.push_str(&tr!("This software is developped by:"));
     for author in authors {
@@ -385,7 +871,7 @@ fn new_line(authors_number: usize) -> String {
 fn add_about(menu: &mut MenuBar) {
     menu.add(&entry_label(&TopLevelMenu::Help, &Submenu::About, None), Shortcut::Ctrl | 'h', MenuFlag::Normal, Box::new(|_: &mut MenuBar| {
         let about = about();
-        display_window(490, 510, &tr!("About"), &about, true, 460, None);
+        display_window(490, 550, &tr!("About"), &about, true, 500, None);
     }));
 }
 
@@ -436,13 +922,27 @@ impl fmt::Display for TopLevelMenu {
 /// The submenus
 enum Submenu {
     New,
+    NewFromSeed,
+    DailyPuzzle,
+    PuzzlePacks,
+    OpenPuzzle,
+    SavePuzzle,
     Undo,
     Redo,
+    Hint,
+    Check,
+    Notes,
+    SetCheckpoint,
+    History,
     BestScores,
+    DailyScores,
     Quit,
     Size,
     Difficulty,
     Sounds,
+    CheckImmediately,
+    AutoAdvance,
+    HighlightConflicts,
     Theme,
     Colors,
     About,
@@ -453,13 +953,27 @@ impl fmt::Display for Submenu {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let printable = match *self {
             Submenu::New => tr!("New"),
+            Submenu::NewFromSeed => tr!("New from seed"),
+            Submenu::DailyPuzzle => tr!("Daily puzzle"),
+            Submenu::PuzzlePacks => tr!("Puzzle packs…"),
+            Submenu::OpenPuzzle => tr!("Open puzzle…"),
+            Submenu::SavePuzzle => tr!("Save puzzle as…"),
             Submenu::Undo => tr!("Undo"),
             Submenu::Redo => tr!("Redo"),
+            Submenu::Hint => tr!("Hint"),
+            Submenu::Check => tr!("Check"),
+            Submenu::Notes => tr!("Notes"),
+            Submenu::SetCheckpoint => tr!("Set checkpoint…"),
+            Submenu::History => tr!("History…"),
             Submenu::BestScores => tr!("Best scores"),
+            Submenu::DailyScores => tr!("Daily scores"),
             Submenu::Quit => tr!("Quit"),
             Submenu::Size => tr!("Size"),
             Submenu::Difficulty => tr!("Difficulty"),
             Submenu::Sounds => tr!("Sounds"),
+            Submenu::CheckImmediately => tr!("Check immediately"),
+            Submenu::AutoAdvance => tr!("Auto-advance"),
+            Submenu::HighlightConflicts => tr!("Highlight conflicts"),
             Submenu::Theme => tr!("Theme"),
             Submenu::Colors => tr!("Colors"),
             Submenu::About => tr!("About"),
@@ -471,6 +985,8 @@ impl fmt::Display for Submenu {
 
 const MENU_HEIGHT: i32 = 40;
 const BUTTON_WIDTH: i32 = 70;
+const PACKS_DIR: &str = "packs";
+const PUZZLE_EXTENSIONS: [&str; 3] = ["txt", "json", "line"];
 
 const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 const HOMEPAGE: &'static str = env!("CARGO_PKG_HOMEPAGE");
@@ -3,15 +3,17 @@
 //! `gui` contains the functions that handles the GUI
 
 mod changing;
+mod generator;
+mod grid;
 mod menu;
 mod sound;
 mod timer;
 mod user_data;
 
 use std::{cell::RefCell, path::Path, rc::Rc};
-use fltk::{app::{App, AppScheme}, button::ReturnButton, enums::{Align, Color}, group::Scroll, image::{PngImage, SvgImage}, frame::Frame, menu::MenuBar, prelude::{GroupExt, ImageExt, WidgetBase, WidgetExt, WindowExt}, window::{DoubleWindow, MenuWindow}};
+use fltk::{app::{App, AppScheme, screen_work_area}, button::ReturnButton, dialog::choice2_default, enums::{Align, Color}, group::Scroll, image::{PngImage, SvgImage}, frame::Frame, menu::MenuBar, prelude::{GroupExt, ImageExt, MenuExt, WidgetBase, WidgetExt, WindowExt}, window::{DoubleWindow, MenuWindow}};
 use tr::tr;
-use user_data::UserPrefs;
+use user_data::{UserPrefs, SavedGame};
 use changing::ChangingPart;
 use lazy_static::lazy_static;
 
@@ -28,10 +30,20 @@ impl Game {
     /// Returns the GUI of the game
     pub fn new() -> Game {
         let user_prefs = UserPrefs::new();
-        let (app, window) = Game::init_gui(&user_prefs.theme());
+        let (app, mut window) = Game::init_gui(&user_prefs.theme());
         let menu = menu::init(window.width());
         let user_prefs = Rc::new(RefCell::new(user_prefs));
         let changing = Rc::new(RefCell::new(ChangingPart::new(menu.height(), window.width(), window.height())));
+        ChangingPart::warm_up(&user_prefs, &changing);
+        Game::add_resize_handler(&mut window, &menu, &changing);
+        Game::restore_geometry(&mut window, &user_prefs.borrow());
+        let cloned_changing = Rc::clone(&changing);
+        let cloned_prefs = Rc::clone(&user_prefs);
+        window.set_callback(move |window: &mut MenuWindow| {
+            ChangingPart::save_game(&cloned_changing);
+            cloned_prefs.borrow_mut().set_geometry(window.x(), window.y(), window.w(), window.h());
+            window.hide();
+        });
         Game {
             user_prefs,
             app,
@@ -52,6 +64,18 @@ impl Game {
         menu::set_menu_items(&mut self.menu, &self.user_prefs);
     }
 
+    /// Offers to resume the game saved when quitting, if there is one
+    pub fn offer_saved_game(&mut self) {
+        let mut saved_game = SavedGame::new();
+        if let Some(binero) = saved_game.binero() {
+            let answer = choice2_default(&tr!("Do you want to resume the saved game?"), &tr!("No"), &tr!("Yes"), "");
+            if answer == Some(1) {
+                ChangingPart::start_game(&self.user_prefs, &self.changing, binero, saved_game.duration(), saved_game.hints(), saved_game.daily(), saved_game.pack());
+            }
+            saved_game.clear();
+        }
+    }
+
     /// Runs the game
     pub fn run_app(&self) {
         self.app.borrow().run().unwrap();
@@ -65,13 +89,65 @@ impl Game {
     fn init_gui(theme: &AppScheme) -> (Rc<RefCell<App>>, MenuWindow) {
         let app = App::default();
         app.with_scheme(*theme);
-        let mut window = MenuWindow::new(0, 0, 700, 552, "YABinero");
+        let mut window = MenuWindow::new(0, 0, WINDOW_WIDTH, WINDOW_HEIGHT, "YABinero");
         window = init_window(window, false);
+        window.make_resizable(true);
+        window.size_range(WINDOW_WIDTH * 3 / 4, WINDOW_HEIGHT * 3 / 4, 0, 0);
         if let Ok(icon) = PngImage::load(&Path::new("icons").join("icon.png")) {
             window.set_icon(Some(icon));
         }
         (Rc::new(RefCell::new(app)), window)
     }
+
+    /// Scales the texts of the menu bar and of the changing part when the window is resized, the
+    /// grid being scaled by itself
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - the main window
+    /// * `menu` - the menu bar
+    /// * `changing` - the changing part of the GUI
+    fn add_resize_handler(window: &mut MenuWindow, menu: &MenuBar, changing: &Rc<RefCell<ChangingPart>>) {
+        let mut cloned_menu = menu.clone();
+        let cloned_changing = Rc::clone(changing);
+        window.resize_callback(move |_: &mut MenuWindow, _: i32, _: i32, width: i32, height: i32| {
+            cloned_menu.set_text_size(scaled(LABEL_SIZE, width, height));
+            ChangingPart::scale_texts(&cloned_changing, width, height);
+        });
+    }
+
+    /// Gives the window the position and the size it had when the game was quit or, the first
+    /// time, a size fitting the screen, so that the grid is readable on large screens (the
+    /// sizes being given in FLTK units, the scale of HiDPI screens is already applied)
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - the main window
+    /// * `user_prefs` - the user's preferences
+    fn restore_geometry(window: &mut MenuWindow, user_prefs: &UserPrefs) {
+        match user_prefs.geometry() {
+            Some((x, y, width, height)) => window.resize(x, y, width, height),
+            None => {
+                let (screen_x, screen_y, screen_width, screen_height) = screen_work_area(0);
+                let scale = (screen_width as f64 * 0.75 / WINDOW_WIDTH as f64).min(screen_height as f64 * 0.75 / WINDOW_HEIGHT as f64).max(1.0);
+                let width = (WINDOW_WIDTH as f64 * scale) as i32;
+                let height = (WINDOW_HEIGHT as f64 * scale) as i32;
+                window.resize(screen_x + (screen_width - width) / 2, screen_y + (screen_height - height) / 2, width, height);
+            },
+        }
+    }
+}
+
+/// Returns the size of a text scaled like the main window
+///
+/// # Arguments
+///
+/// * `size` - the size of the text in a window of default size
+/// * `width` - the width of the main window
+/// * `height` - the height of the main window
+fn scaled(size: i32, width: i32, height: i32) -> i32 {
+    let scale = (width as f64 / WINDOW_WIDTH as f64).min(height as f64 / WINDOW_HEIGHT as f64);
+    ((size as f64 * scale).round() as i32).max(1)
 }
 
 /// Sets some parameters to a window
@@ -184,6 +260,15 @@ fn display_message(msg: &str) {
     display_window(500, 150, "", msg, true, 100, Some("ok"));
 }
 
+/// Displays a popup with a hint
+///
+/// # Arguments
+///
+/// * `msg` - the hint
+fn display_hint(msg: &str) {
+    display_window(640, 150, &tr!("Hint"), msg, true, 100, None);
+}
+
 /// Shows a window
 ///
 /// # Arguments
@@ -196,12 +281,19 @@ fn show(window: &mut impl WindowExt) {
 
 const BG_COLOR: Color = Color::Light2;
 const SELECT_COLOR: Color = Color::Dark3;
-const RO_SELECT_COLOR: Color = Color::Dark1;
+const HINT_COLOR: Color = Color::Yellow;
+const NOTE_COLOR: Color = Color::from_rgb(170, 210, 255);
+const ERROR_COLOR: Color = Color::from_rgb(255, 160, 160);
+const CONFLICT_COLOR: Color = Color::from_rgb(255, 215, 215);
+const CURSOR_COLOR: Color = Color::from_rgb(40, 110, 200);
 
 lazy_static! {
     static ref FG_COLOR: Color = Color::from_rgb(16, 16, 16);
     static ref RO_FG_COLOR: Color = Color::from_rgb(88, 88, 88);
 }
 
+const WINDOW_WIDTH: i32 = 700;
+const WINDOW_HEIGHT: i32 = 576;
+const LABEL_SIZE: i32 = 14;
 const BUTTON_HEIGHT: i32 = 40;
 const RET_BUTTON_WIDTH: i32 = 100;
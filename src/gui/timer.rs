@@ -26,8 +26,12 @@ impl Timer {
     }
 
     /// Starts the timer
-    pub fn start(&mut self) -> Sender<bool> {
-        self.reset();
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - the duration already elapsed
+    pub fn start(&mut self, duration: u64) -> Sender<bool> {
+        self.reset(duration);
         let cloned_timer = Arc::clone(&self.timer);
         let cloned_old_duration = Arc::clone(&self.old_duration);
         let cloned_curr_start = Arc::clone(&self.curr_start);
@@ -61,6 +65,19 @@ impl Timer {
         *self.old_duration.lock().unwrap()
     }
 
+    /// Returns the elapsed duration, including the current period if the timer is running
+    ///
+    /// # Arguments
+    ///
+    /// * `running` - whether or not the timer is running
+    pub fn elapsed(&self, running: bool) -> u64 {
+        let mut result = self.duration();
+        if running {
+            result += self.curr_start.lock().unwrap().elapsed().as_secs();
+        }
+        result
+    }
+
     /// Formats a duration
     ///
     /// # Arguments
@@ -75,6 +92,15 @@ impl Timer {
         Timer::display_duration(&self.timer, self.duration());
     }
 
+    /// Sets the size of the text of the timer
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the text
+    pub fn set_label_size(&self, size: i32) {
+        self.timer.lock().unwrap().set_label_size(size);
+    }
+
     /// Displays a duration in the GUI
     ///
     /// # Arguments
@@ -95,22 +121,27 @@ impl Timer {
     /// * `y` - the value in y-axis
     /// * `width` - the width
     fn init_timer(x: i32, y: i32, width: i32) -> Arc<Mutex<Frame>> {
-        let mut timer = Frame::new(x, y, width, 120, "00:00");
+        let mut timer = Frame::new(x, y, width, Timer::HEIGHT, "00:00");
         set_svg(&mut timer, "chrono.svg");
         timer.hide();
         Arc::new(Mutex::new(timer))
     }
 
     /// Resets the timer
-    fn reset(&mut self) {
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - the duration already elapsed
+    fn reset(&mut self, duration: u64) {
         let mut curr_start = self.curr_start.lock().unwrap();
         *curr_start = Instant::now();
         let mut old_duration = self.old_duration.lock().unwrap();
-        *old_duration = 0;
+        *old_duration = duration;
         self.timer.lock().unwrap().show();
     }
 
     pub const WAITING: u64 = 100;
+    pub const HEIGHT: i32 = 100;
 
     const WAITING_DURATION: Duration = Duration::from_millis(Timer::WAITING);
 }
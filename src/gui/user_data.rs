@@ -6,10 +6,10 @@ use std::{cell::RefCell, collections::HashMap, rc::Rc, str::FromStr};
 use preferences::{AppInfo, PreferencesMap, Preferences};
 use fltk::{app::AppScheme, enums::Color, utils::hex2rgb};
 use tr::tr;
-use enum_iterator::all;
 use chrono::Local;
 use crate::gui::{FG_COLOR, RO_FG_COLOR, display_alert, timer::Timer};
-use crate::enums::{Difficulty, Size};
+use yabinero::engine::{Binero, pack::Pack};
+use yabinero::enums::{Difficulty, Size};
 
 /// The user's preferences
 pub struct UserPrefs {
@@ -32,6 +32,9 @@ impl UserPrefs {
             faves.insert("theme".to_owned(), "Gtk".to_owned());
             faves.insert("color".to_owned(), UserPrefs::color_as_string(&FG_COLOR));
             faves.insert("ro_color".to_owned(), UserPrefs::color_as_string(&RO_FG_COLOR));
+            faves.insert("check_immediately".to_owned(), "false".to_owned());
+            faves.insert("auto_advance".to_owned(), "false".to_owned());
+            faves.insert("highlight_conflicts".to_owned(), "false".to_owned());
             let result = UserPrefs {
                 faves,
             };
@@ -43,7 +46,7 @@ impl UserPrefs {
     /// Returns the current size
     pub fn size(&self) -> Size {
         if let Some(size) = self.faves.get("size") {
-            if let Some(result) = Size::from_str(size) {
+            if let Ok(result) = Size::from_str(size) {
                 result
             } else {
                 UserPrefs::bad_size()
@@ -59,14 +62,14 @@ impl UserPrefs {
     ///
     /// * `size` - a size
     pub fn set_size(&mut self, size: Size) {
-        self.faves.insert("size".to_owned(), format!("{}", size.as_u8()));
+        self.faves.insert("size".to_owned(), format!("{}", size));
         self.save(true);
     }
 
     /// Returns the current difficulty
     pub fn difficulty(&self) -> Difficulty {
         if let Some(difficulty) = self.faves.get("difficulty") {
-            if let Some(result) = Difficulty::from_str(difficulty) {
+            if let Ok(result) = Difficulty::from_str(difficulty) {
                 result
             } else {
                 UserPrefs::bad_difficulty()
@@ -109,6 +112,102 @@ impl UserPrefs {
         self.save(true);
     }
 
+    /// Returns whether or not the wrong values must be marked as soon as they are typed
+    pub fn check_immediately(&self) -> bool {
+        if let Some(check_immediately) = self.faves.get("check_immediately") {
+            if let Ok(result) = bool::from_str(check_immediately) {
+                result
+            } else {
+                UserPrefs::bad_check_immediately()
+            }
+        } else {
+            false
+        }
+    }
+
+    /// Sets whether or not the wrong values must be marked as soon as they are typed
+    ///
+    /// # Arguments
+    ///
+    /// * `check_immediately` - whether or not the wrong values must be marked as soon as they are typed
+    pub fn set_check_immediately(&mut self, check_immediately: bool) {
+        self.faves.insert("check_immediately".to_owned(), format!("{}", check_immediately));
+        self.save(true);
+    }
+
+    /// Returns whether or not the focus must move to the next empty box after a value is typed
+    pub fn auto_advance(&self) -> bool {
+        if let Some(auto_advance) = self.faves.get("auto_advance") {
+            if let Ok(result) = bool::from_str(auto_advance) {
+                result
+            } else {
+                UserPrefs::bad_auto_advance()
+            }
+        } else {
+            false
+        }
+    }
+
+    /// Sets whether or not the focus must move to the next empty box after a value is typed
+    ///
+    /// # Arguments
+    ///
+    /// * `auto_advance` - whether or not the focus must move to the next empty box after a value is typed
+    pub fn set_auto_advance(&mut self, auto_advance: bool) {
+        self.faves.insert("auto_advance".to_owned(), format!("{}", auto_advance));
+        self.save(true);
+    }
+
+    /// Returns whether or not the values breaking a rule are kept in the grid with their rows and
+    /// columns highlighted, instead of being refused with a popup
+    pub fn highlight_conflicts(&self) -> bool {
+        if let Some(highlight_conflicts) = self.faves.get("highlight_conflicts") {
+            if let Ok(result) = bool::from_str(highlight_conflicts) {
+                result
+            } else {
+                UserPrefs::bad_highlight_conflicts()
+            }
+        } else {
+            false
+        }
+    }
+
+    /// Sets whether or not the values breaking a rule are kept in the grid with their rows and
+    /// columns highlighted
+    ///
+    /// # Arguments
+    ///
+    /// * `highlight_conflicts` - whether or not the values breaking a rule are kept and highlighted
+    pub fn set_highlight_conflicts(&mut self, highlight_conflicts: bool) {
+        self.faves.insert("highlight_conflicts".to_owned(), format!("{}", highlight_conflicts));
+        self.save(true);
+    }
+
+    /// Returns the position and the size of the main window when the game was quit, or `None` if
+    /// they were never saved
+    pub fn geometry(&self) -> Option<(i32, i32, i32, i32)> {
+        let geometry = self.faves.get("geometry")?;
+        let values: Vec<i32> = geometry.split(',').filter_map(|value| value.parse().ok()).collect();
+        if let [x, y, width, height] = values[..] {
+            Some((x, y, width, height))
+        } else {
+            UserPrefs::bad_geometry()
+        }
+    }
+
+    /// Sets the position and the size of the main window
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - the x-axis of the window
+    /// * `y` - the y-axis of the window
+    /// * `width` - the width of the window
+    /// * `height` - the height of the window
+    pub fn set_geometry(&mut self, x: i32, y: i32, width: i32, height: i32) {
+        self.faves.insert("geometry".to_owned(), format!("{},{},{},{}", x, y, width, height));
+        self.save(true);
+    }
+
     /// Returns the current theme
     pub fn theme(&self) -> AppScheme {
         if let Some(theme) = self.faves.get("theme") {
@@ -234,7 +333,7 @@ impl UserPrefs {
     /// Returns the default size when the size cannot be read from the user's preferences
     fn bad_size() -> Size {
         display_alert(&tr!("Bad size!"));
-        Size::Side6
+        Size::square(Size::MIN_SIDE).unwrap()
     }
 
     /// Returns the default difficulty when the difficulty cannot be read from the user's preferences
@@ -249,6 +348,34 @@ impl UserPrefs {
         true
     }
 
+    /// Returns `false` when the choice about marking the wrong values immediately cannot be read from
+    /// the user's preferences
+    fn bad_check_immediately() -> bool {
+        display_alert(&tr!("Unable to know whether or not the wrong values must be marked immediately!"));
+        false
+    }
+
+    /// Returns `false` when the choice about moving to the next empty box cannot be read from the
+    /// user's preferences
+    fn bad_auto_advance() -> bool {
+        display_alert(&tr!("Unable to know whether or not the focus must move to the next empty box!"));
+        false
+    }
+
+    /// Returns `false` when the choice about highlighting the values breaking a rule cannot be read
+    /// from the user's preferences
+    fn bad_highlight_conflicts() -> bool {
+        display_alert(&tr!("Unable to know whether or not the values breaking a rule must be highlighted!"));
+        false
+    }
+
+    /// Returns `None` when the position and the size of the main window cannot be read from the
+    /// user's preferences
+    fn bad_geometry() -> Option<(i32, i32, i32, i32)> {
+        display_alert(&tr!("Bad window geometry!"));
+        None
+    }
+
     /// Returns the default theme when the theme cannot be read from the user's preferences
     fn bad_theme() -> AppScheme {
         display_alert(&tr!("Bad theme!"));
@@ -266,21 +393,8 @@ pub struct BestScores {
 impl BestScores {
     /// Returns the best scores
     pub fn new() -> BestScores {
-        let mut scores = HashMap::new();
-        for size in all::<Size>() {
-            for difficulty in all::<Difficulty>() {
-                let key = BestScores::key(size, difficulty);
-                let load_result = PreferencesMap::<String>::load(&APP_INFO, &key);
-                let score = if load_result.is_ok() {
-                    load_result.unwrap()
-                } else {
-                    PreferencesMap::new()
-                };
-                scores.insert(key, score);
-            }
-        }
         BestScores {
-            scores,
+            scores: HashMap::new(),
         }
     }
 
@@ -290,15 +404,15 @@ impl BestScores {
     ///
     /// * `size` - a size
     /// * `difficulty` - a difficulty
-    pub fn best_scores(&self, size: Size, difficulty: Difficulty) -> String {
+    pub fn best_scores(&mut self, size: Size, difficulty: Difficulty) -> String {
         let key = BestScores::key(size, difficulty);
-        let best_scores = self.scores.get(&key).unwrap();
+        let best_scores = self.scores.entry(key.clone()).or_insert_with(|| BestScores::load(&key));
         let mut result = "".to_owned();
         let mut ranking: u8 = 1;
         while ranking <= BestScores::MAX_BEST_SCORE {
             if let Some(score) = best_scores.get(&format!("{}", ranking)) {
                 result.push_str(&format!("{:02}\t", ranking));
-                result.push_str(score);
+                result.push_str(&BestScores::displayed(score));
             }
             result.push_str("\n");
             ranking += 1;
@@ -313,10 +427,11 @@ impl BestScores {
     /// * `size` - a size
     /// * `difficulty` - a difficulty
     /// * `timer` - a timer
-    pub fn add_best_score(&mut self, size: Size, difficulty: Difficulty, timer: &Rc<RefCell<Timer>>) {
+    /// * `hints` - the number of hints used
+    pub fn add_best_score(&mut self, size: Size, difficulty: Difficulty, timer: &Rc<RefCell<Timer>>, hints: u16) {
         let duration = timer.borrow().duration();
         let key = BestScores::key(size, difficulty);
-        let best_scores = self.scores.get_mut(&key).unwrap();
+        let best_scores = self.scores.entry(key.clone()).or_insert_with(|| BestScores::load(&key));
         let mut ranking = String::from("1");
         let mut rank: u8 = 1;
         while rank <= BestScores::MAX_BEST_SCORE {
@@ -333,7 +448,7 @@ impl BestScores {
             }
         }
         if rank <= BestScores::MAX_BEST_SCORE {
-            let mut old_score = Some(BestScores::score(duration));
+            let mut old_score = Some(BestScores::score(duration, hints));
             while BestScores::ranking_as_u8(&ranking) <= BestScores::MAX_BEST_SCORE && old_score.is_some() {
                 old_score = best_scores.insert(ranking.clone(), old_score.unwrap());
                 ranking = format!("{}", BestScores::ranking_as_u8(&ranking) + 1);
@@ -358,11 +473,24 @@ impl BestScores {
     /// # Arguments
     ///
     /// * `duration` - a duration
-    fn score(duration: u64) -> String {
-        let mut score = Timer::format(duration);
-        score.push_str("\t\t");
-        score.push_str(&Local::now().format("%Y-%m-%d %H:%M:%S").to_string());
-        score
+    /// * `hints` - the number of hints used
+    fn score(duration: u64, hints: u16) -> String {
+        format!("{}\t\t{}\t\t{}", Timer::format(duration), Local::now().format("%Y-%m-%d %H:%M:%S"), hints)
+    }
+
+    /// Returns a score as it is displayed, the number of hints being translated or removed when
+    /// no hint was used
+    ///
+    /// # Arguments
+    ///
+    /// * `score` - a score
+    fn displayed(score: &str) -> String {
+        let fields: Vec<String> = score.split("\t\t").filter_map(|field| match field.parse::<u16>() {
+            Ok(0) => None,
+            Ok(hints) => Some(tr!("Hints: {}", hints)),
+            Err(_) => Some(field.to_owned()),
+        }).collect();
+        fields.join("\t\t")
     }
 
     /// Saves some best scores
@@ -406,7 +534,269 @@ impl BestScores {
         format!("{}-{:?}", size, difficulty)
     }
 
+    /// Returns the scores saved in the user's preferences directory under a key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key of the scores
+    fn load(key: &str) -> PreferencesMap<String> {
+        PreferencesMap::<String>::load(&APP_INFO, key).unwrap_or_default()
+    }
+
     const MAX_BEST_SCORE: u8 = 10;
 }
 
+/// The best time of each daily puzzle
+pub struct DailyScores {
+    scores: HashMap<String, PreferencesMap<String>>,
+}
+
+impl DailyScores {
+    /// Returns the daily scores
+    pub fn new() -> DailyScores {
+        DailyScores {
+            scores: HashMap::new(),
+        }
+    }
+
+    /// Returns the latest daily scores for a size
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - a size
+    pub fn daily_scores(&mut self, size: Size) -> String {
+        let key = DailyScores::key(size);
+        let daily_scores = self.scores.entry(key.clone()).or_insert_with(|| BestScores::load(&key));
+        let mut dates: Vec<&String> = daily_scores.keys().collect();
+        dates.sort_unstable_by(|a, b| b.cmp(a));
+        let mut result = "".to_owned();
+        for date in dates.into_iter().take(DailyScores::MAX_DAILY_SCORE) {
+            result.push_str(date);
+            result.push_str("\t\t");
+            result.push_str(&BestScores::displayed(&daily_scores[date]));
+            result.push('\n');
+        }
+        result
+    }
+
+    /// Adds the score of a daily puzzle if it is the best one for that day
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - a size
+    /// * `date` - the date of the daily puzzle
+    /// * `timer` - a timer
+    /// * `hints` - the number of hints used
+    pub fn add_daily_score(&mut self, size: Size, date: &str, timer: &Rc<RefCell<Timer>>, hints: u16) {
+        let duration = timer.borrow().duration();
+        let key = DailyScores::key(size);
+        let daily_scores = self.scores.entry(key.clone()).or_insert_with(|| BestScores::load(&key));
+        if daily_scores.get(date).is_some_and(|score| BestScores::duration(score) <= duration) {
+            return;
+        }
+        daily_scores.insert(date.to_owned(), format!("{}\t\t{}", Timer::format(duration), hints));
+        let save_result = daily_scores.save(&APP_INFO, key);
+        if save_result.is_err() {
+            display_alert(&tr!("Daily scores cannot be saved!"));
+        }
+    }
+
+    /// Returns the date of today's daily puzzle
+    pub fn today() -> String {
+        Local::now().format("%Y-%m-%d").to_string()
+    }
+
+    /// Returns the seed of the daily puzzle of a date for a size
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - a date, as returned by `DailyScores::today`
+    /// * `size` - a size
+    pub fn seed(date: &str, size: Size) -> u64 {
+        let date: u64 = date.replace('-', "").parse().unwrap();
+        let seed = date * 100 + size.width() as u64;
+        if size.width() == size.height() {
+            seed
+        } else {
+            seed * 100 + size.height() as u64
+        }
+    }
+
+    /// Returns the key for a size
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - a size
+    fn key(size: Size) -> String {
+        format!("daily-{}", size)
+    }
+
+    const MAX_DAILY_SCORE: usize = 10;
+}
+
+/// The best time of each binero of the packs
+pub struct PackScores {
+    scores: PreferencesMap<String>,
+}
+
+impl PackScores {
+    /// Returns the scores of the packs
+    pub fn new() -> PackScores {
+        let load_result = PreferencesMap::<String>::load(&APP_INFO, PackScores::PREFS_KEY);
+        PackScores {
+            scores: load_result.unwrap_or_default(),
+        }
+    }
+
+    /// Returns the best score of a binero of a pack, as it is displayed, if it has been solved
+    ///
+    /// # Arguments
+    ///
+    /// * `pack` - the name of a pack
+    /// * `id` - the id of the binero in the pack
+    pub fn pack_score(&self, pack: &str, id: &str) -> Option<String> {
+        self.scores.get(&PackScores::key(pack, id)).map(|score| BestScores::displayed(score))
+    }
+
+    /// Returns the number of bineros of a pack that have been solved
+    ///
+    /// # Arguments
+    ///
+    /// * `pack` - a pack
+    pub fn solved(&self, pack: &Pack) -> usize {
+        pack.puzzles().iter().filter(|puzzle| self.pack_score(pack.name(), puzzle.id()).is_some()).count()
+    }
+
+    /// Adds the score of a binero of a pack if it is the best one for that binero
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key of the binero, as returned by `PackScores::key`
+    /// * `timer` - a timer
+    /// * `hints` - the number of hints used
+    pub fn add_pack_score(&mut self, key: &str, timer: &Rc<RefCell<Timer>>, hints: u16) {
+        let duration = timer.borrow().duration();
+        if self.scores.get(key).is_some_and(|score| BestScores::duration(score) <= duration) {
+            return;
+        }
+        self.scores.insert(key.to_owned(), BestScores::score(duration, hints));
+        let save_result = self.scores.save(&APP_INFO, PackScores::PREFS_KEY);
+        if save_result.is_err() {
+            display_alert(&tr!("Pack scores cannot be saved!"));
+        }
+    }
+
+    /// Returns the key of a binero of a pack
+    ///
+    /// # Arguments
+    ///
+    /// * `pack` - the name of a pack
+    /// * `id` - the id of the binero in the pack
+    pub fn key(pack: &str, id: &str) -> String {
+        format!("{}/{}", pack, id)
+    }
+
+    const PREFS_KEY: &'static str = "pack-scores";
+}
+
+/// The game saved when quitting, so that it can be resumed at the next launch
+pub struct SavedGame {
+    state: PreferencesMap<String>,
+}
+
+impl SavedGame {
+    /// Returns the saved game
+    pub fn new() -> SavedGame {
+        let load_result = PreferencesMap::<String>::load(&APP_INFO, SavedGame::PREFS_KEY);
+        SavedGame {
+            state: load_result.unwrap_or_default(),
+        }
+    }
+
+    /// Returns the saved binero or `None` if there is no saved game or if it cannot be restored
+    pub fn binero(&self) -> Option<Binero> {
+        let size = Size::from_str(self.state.get("size")?).ok()?;
+        let difficulty = Difficulty::from_str(self.state.get("difficulty")?).ok()?;
+        let seed = match self.state.get("seed") {
+            Some(seed) => Some(seed.parse().ok()?),
+            None => None,
+        };
+        let binero = Binero::restore(size, difficulty, seed, self.state.get("givens")?, self.state.get("grid")?, self.state.get("solution")?, self.state.get("history")?)?;
+        match self.state.get("notes") {
+            Some(notes) => binero.with_notes(notes),
+            None => Some(binero),
+        }
+    }
+
+    /// Returns the duration elapsed in the saved game
+    pub fn duration(&self) -> u64 {
+        self.state.get("duration").and_then(|duration| duration.parse().ok()).unwrap_or(0)
+    }
+
+    /// Returns the number of hints used in the saved game
+    pub fn hints(&self) -> u16 {
+        self.state.get("hints").and_then(|hints| hints.parse().ok()).unwrap_or(0)
+    }
+
+    /// Returns the date of the saved game if it is a daily puzzle
+    pub fn daily(&self) -> Option<String> {
+        self.state.get("daily").cloned()
+    }
+
+    /// Returns the key of the binero of the saved game if it comes from a pack
+    pub fn pack(&self) -> Option<String> {
+        self.state.get("pack").cloned()
+    }
+
+    /// Saves a game
+    ///
+    /// # Arguments
+    ///
+    /// * `binero` - a binero
+    /// * `duration` - the duration elapsed in the game
+    /// * `hints` - the number of hints used in the game
+    /// * `daily` - the date of the game if it is a daily puzzle
+    /// * `pack` - the key of the binero if it comes from a pack
+    pub fn save(&mut self, binero: &Binero, duration: u64, hints: u16, daily: Option<&str>, pack: Option<&str>) {
+        self.state.insert("size".to_owned(), format!("{}", binero.size()));
+        self.state.insert("difficulty".to_owned(), format!("{:?}", binero.difficulty()));
+        match binero.seed() {
+            Some(seed) => self.state.insert("seed".to_owned(), format!("{}", seed)),
+            None => self.state.remove("seed"),
+        };
+        self.state.insert("givens".to_owned(), binero.givens().to_compact_string());
+        self.state.insert("grid".to_owned(), binero.grid().to_compact_string());
+        self.state.insert("solution".to_owned(), binero.solution().to_compact_string());
+        self.state.insert("notes".to_owned(), binero.notes().to_compact_string());
+        self.state.insert("history".to_owned(), binero.history().to_compact_string());
+        self.state.insert("duration".to_owned(), format!("{}", duration));
+        self.state.insert("hints".to_owned(), format!("{}", hints));
+        match daily {
+            Some(date) => self.state.insert("daily".to_owned(), date.to_owned()),
+            None => self.state.remove("daily"),
+        };
+        match pack {
+            Some(key) => self.state.insert("pack".to_owned(), key.to_owned()),
+            None => self.state.remove("pack"),
+        };
+        self.write();
+    }
+
+    /// Removes the saved game
+    pub fn clear(&mut self) {
+        self.state.clear();
+        self.write();
+    }
+
+    /// Writes the saved game in the user's preferences directory
+    fn write(&self) {
+        let save_result = self.state.save(&APP_INFO, SavedGame::PREFS_KEY);
+        if save_result.is_err() {
+            display_alert(&tr!("The game cannot be saved!"));
+        }
+    }
+
+    const PREFS_KEY: &'static str = "saved_game";
+}
+
 const APP_INFO: AppInfo = AppInfo{name: "yabinero", author: "Nicolas Salguero"};
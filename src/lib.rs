@@ -0,0 +1,10 @@
+//! # Yet Another Binero puzzle library
+//!
+//! `yabinero` generates, rates, solves, imports and exports binero puzzles without any GUI, so
+//! that it can be embedded in other tools
+
+pub mod engine;
+pub mod enums;
+
+pub use engine::{Binero, ParseError, grid::Grid, history::History, pack::{Pack, PackPuzzle}, solver::{Rating, Solver, Step, Technique}};
+pub use enums::{Difficulty, ParseEnumError, Size, Value};
@@ -1,22 +1,37 @@
-#![windows_subsystem = "windows"]
+#![cfg_attr(feature = "gui", windows_subsystem = "windows")]
 
 //! # Yet Another Binero puzzle game
 
-mod engine;
-mod enums;
+mod cli;
+#[cfg(feature = "gui")]
 mod gui;
+mod translation;
 
-use std::{fs::File, io::Result, path::PathBuf};
+use std::{env, fs::File, io::Result, path::PathBuf, process};
 use locale_config::Locale;
 use gettext::Catalog;
 use tr::set_translator;
+#[cfg(feature = "gui")]
 use gui::Game;
 
 fn main() {
     tr_init();
+    let args: Vec<String> = env::args().skip(1).collect();
+    #[cfg(feature = "gui")]
+    if args.is_empty() {
+        play();
+        return;
+    }
+    process::exit(cli::run(&args));
+}
+
+/// Starts the game
+#[cfg(feature = "gui")]
+fn play() {
     let mut game = Game::new();
     game.show_window();
     game.add_menu_entries();
+    game.offer_saved_game();
     game.run_app();
 }
 
@@ -43,7 +58,7 @@ fn open_mo_file(locale: &str) -> Result<File> {
     loc_path.push("locale");
     loc_path.push(locale);
     loc_path.push("LC_MESSAGES");
-    loc_path.push(&format!("{}{}", env!("CARGO_PKG_NAME"), ".mo"));
+    loc_path.push(format!("{}{}", env!("CARGO_PKG_NAME"), ".mo"));
     File::open(loc_path)
 }
 
@@ -0,0 +1,50 @@
+//! # Translation
+//!
+//! `translation` contains the translations of the texts of the `yabinero` library, which does not
+//! depend on gettext
+
+use tr::tr;
+use yabinero::engine::{ParseError, solver::Technique};
+use yabinero::enums::Difficulty;
+
+/// A text of the library that can be translated into the user's language
+pub trait Translate {
+    /// Returns the text translated into the user's language
+    fn translate(&self) -> String;
+}
+
+impl Translate for Difficulty {
+    fn translate(&self) -> String {
+        match *self {
+            Difficulty::Beginner => tr!("Beginner"),
+            Difficulty::Easy => tr!("Easy"),
+            Difficulty::Medium => tr!("Medium"),
+            Difficulty::Hard => tr!("Hard"),
+        }
+    }
+}
+
+impl Translate for Technique {
+    fn translate(&self) -> String {
+        match *self {
+            Technique::Pair => tr!("Pair"),
+            Technique::Sandwich => tr!("Sandwich"),
+            Technique::CountCompletion => tr!("Count completion"),
+            Technique::Uniqueness => tr!("Uniqueness"),
+            Technique::LookAhead => tr!("Look-ahead"),
+        }
+    }
+}
+
+impl Translate for ParseError {
+    fn translate(&self) -> String {
+        match *self {
+            ParseError::Size => tr!("The width and the height of the grid must be supported!"),
+            ParseError::Character(c) => tr!("The character '{}' is not allowed in the grid!", c),
+            ParseError::Constraints => tr!("The values of the grid do not respect the rules!"),
+            ParseError::NoSolution => tr!("The grid has no solution!"),
+            ParseError::SeveralSolutions => tr!("The grid has several solutions!"),
+            ParseError::Document => tr!("The document is not a valid pack!"),
+        }
+    }
+}
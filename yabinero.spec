@@ -96,6 +96,9 @@ install -m 0644 locale/fr/LC_MESSAGES/%{name}.mo %{buildroot}/%{_datadir}/games/
 mkdir -p %{buildroot}/%{_datadir}/games/%{name}/sounds
 install -m 0644 sounds/*.ogg %{buildroot}/%{_datadir}/games/%{name}/sounds
 
+mkdir -p %{buildroot}/%{_datadir}/games/%{name}/packs
+install -m 0644 packs/*.json %{buildroot}/%{_datadir}/games/%{name}/packs
+
 mkdir -p %{buildroot}/%{_iconsdir}
 install -m 0644 icons/icon.png %{buildroot}/%{_iconsdir}/%{name}.png
 
@@ -116,6 +119,7 @@ desktop-file-install --vendor="" \
 %{_datadir}/games/%{name}/icons/*.svg
 %{_datadir}/games/%{name}/locale/fr/LC_MESSAGES/%{name}.mo
 %{_datadir}/games/%{name}/sounds/*.ogg
+%{_datadir}/games/%{name}/packs/*.json
 
 %{_iconsdir}/%{name}.png
 